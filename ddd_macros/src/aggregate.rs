@@ -1,7 +1,9 @@
-use crate::entity;
+use crate::{
+    PENDING_EVENTS_ATTR, declare_errors::register_generated_key, entity, generate_id::generate_id,
+    type_name::type_error_prefix,
+};
 use proc_macro::TokenStream;
-use proc_macro2::Span;
-use syn::{Data, DeriveInput, Field, Ident, Type};
+use syn::{Data, DeriveInput, Field};
 
 pub fn generate_aggregate(ast: DeriveInput) -> TokenStream {
     let entity_ast = ast.clone();
@@ -30,44 +32,45 @@ pub fn generate_aggregate(ast: DeriveInput) -> TokenStream {
         .find(|attribute| attribute.path().is_ident("generate_id"));
 
     let generated_id_quote = match generate_id_attribute {
-        Some(attribute) => {
-            let id_type = attribute.parse_args::<Type>().unwrap();
-            let id_identity_name = identity.to_string() + "Id";
-            let id_identity = Ident::new(id_identity_name.as_str(), Span::call_site());
-            quote::quote!(
-                // Generate the ID struct of the struct
-                #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
-                pub struct #id_identity(#id_type);
-
-                impl #id_identity {
-                    pub fn new(id: #id_type) -> Self {
-                        Self(id)
-                    }
+        Some(attribute) => generate_id(&identity, &ast.attrs, &attribute),
+        None => quote::quote!(),
+    };
 
-                    pub fn value_as_ref(&self) -> &#id_type {
-                        &self.0
-                    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-                    pub fn value(self) -> #id_type {
-                        self.0
+    let pending_events_quote = match fields.iter().find(|field| {
+        field
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident(PENDING_EVENTS_ATTR))
+    }) {
+        Some(field) => {
+            let field_ident = field.ident.as_ref().unwrap();
+            quote::quote!(
+                impl #impl_generics kern::building_blocks::records_events::RecordsEvents for #identity #ty_generics #where_clause {
+                    fn pending_events(&self) -> &kern::building_blocks::records_events::PendingEvents {
+                        &self.#field_ident
                     }
-                }
 
-                impl From<#id_type> for #id_identity {
-                    fn from(value: #id_type) -> Self {
-                        Self(value)
+                    fn pending_events_mut(&mut self) -> &mut kern::building_blocks::records_events::PendingEvents {
+                        &mut self.#field_ident
                     }
                 }
-
-                impl kern::building_blocks::ids::AggregateId for #id_identity {}
             )
         }
         None => quote::quote!(),
     };
-    let entity_quote: proc_macro2::TokenStream = entity::generate_entity(entity_ast).into();
-    let identity_name = super::to_snake_case(identity.clone().to_string());
 
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let concurrency_conflict_quote = match type_error_prefix(&identity, &ast.attrs) {
+        Ok(error_prefix) => register_generated_key(
+            &format!("error.{error_prefix}.concurrency-conflict"),
+            "The aggregate was modified by another writer since it was loaded",
+            &identity.to_string(),
+        ),
+        Err(err) => err.to_compile_error(),
+    };
+
+    let entity_quote: proc_macro2::TokenStream = entity::generate_entity(entity_ast).into();
     quote::quote!(
         #generated_id_quote
 
@@ -77,12 +80,19 @@ pub fn generate_aggregate(ast: DeriveInput) -> TokenStream {
                 self.version
             }
 
-            fn type_name() -> &'static str {
-                #identity_name
+            fn increment_version(&mut self) -> Result<(), kern::building_blocks::error::domain_error::DomainError> {
+                self.version = self.version.checked_add(1).ok_or(
+                    kern::building_blocks::aggregate::VERSION_OVERFLOW,
+                )?;
+                Ok(())
             }
 
         }
 
+        #pending_events_quote
+
+        #concurrency_conflict_quote
+
         #entity_quote
     )
     .into()
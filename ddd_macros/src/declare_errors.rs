@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    Attribute, Ident, LitStr, Token, Visibility,
+    parse::{Parse, ParseStream},
+};
+
+/// A declaration of an error: `/// doc` `pub NAME = "error.<type>.<slug>" => "message";`
+struct ErrorDeclaration {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    key: LitStr,
+    message: LitStr,
+}
+
+impl Parse for ErrorDeclaration {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let key = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let message = input.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok(Self {
+            attrs,
+            vis,
+            name,
+            key,
+            message,
+        })
+    }
+}
+
+/// The declarations of a `declare_errors!` invocation
+pub struct ErrorDeclarations(Vec<ErrorDeclaration>);
+
+impl Parse for ErrorDeclarations {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut declarations = Vec::new();
+        while !input.is_empty() {
+            declarations.push(input.parse()?);
+        }
+        Ok(Self(declarations))
+    }
+}
+
+pub fn generate_declare_errors(declarations: ErrorDeclarations) -> TokenStream {
+    match expand(declarations) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(
+    ErrorDeclarations(declarations): ErrorDeclarations,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut keys: HashMap<String, &Ident> = HashMap::new();
+    for declaration in &declarations {
+        let key = declaration.key.value();
+        if !is_valid_key(&key) {
+            return Err(syn::Error::new(
+                declaration.key.span(),
+                format!(
+                    "invalid error key '{key}', expected `error.<type>.<slug>` in lowercase kebab case"
+                ),
+            ));
+        }
+        if let Some(name) = keys.insert(key.clone(), &declaration.name) {
+            return Err(syn::Error::new(
+                declaration.key.span(),
+                format!("the error key '{key}' is already declared by `{name}`"),
+            ));
+        }
+    }
+
+    let constants = declarations.iter().map(|declaration| {
+        let ErrorDeclaration {
+            attrs,
+            vis,
+            name,
+            key,
+            message,
+        } = declaration;
+        let name_string = name.to_string();
+        // Every key exports a symbol, so declaring a key twice fails to compile within a crate
+        // and to link across crates, whether or not the keys are registered
+        let symbol = format!("__kern_error_key.{}", key.value());
+        // The registration expands to nothing unless the `error_catalog` feature of kern is enabled
+        quote! {
+            #(#attrs)*
+            #vis const #name: kern::building_blocks::error::error_detail::ErrorDetail =
+                kern::building_blocks::error::error_detail::ErrorDetail::new_const(#key, #message);
+
+            const _: () = {
+                #[used]
+                #[unsafe(export_name = #symbol)]
+                static UNIQUE_ERROR_KEY: u8 = 0;
+            };
+
+            kern::__register_error_key!(#key, #message, #name_string, module_path!());
+        }
+    });
+
+    Ok(quote! {
+        #(#constants)*
+    })
+}
+
+/// Registers an error key that a derive generates in the `ErrorCatalog`. Unlike a declared key, a
+/// generated key exports no symbol, because types in different modules may share an error prefix
+/// # Arguments
+/// * `key` - The generated key
+/// * `message` - The descriptive message of the key
+/// * `name` - The name of the type the key is generated for
+pub(crate) fn register_generated_key(
+    key: &str,
+    message: &str,
+    name: &str,
+) -> proc_macro2::TokenStream {
+    quote! {
+        kern::__register_error_key!(#key, #message, #name, module_path!());
+    }
+}
+
+/// Returns true if the key has the format `error.<type>.<slug>`, where the type and the slug are
+/// lowercase kebab case
+fn is_valid_key(key: &str) -> bool {
+    let is_kebab_case = |segment: &str| {
+        !segment.is_empty()
+            && segment.split('-').all(|word| {
+                !word.is_empty()
+                    && word
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+            })
+    };
+    let mut segments = key.split('.');
+    matches!(
+        (segments.next(), segments.next(), segments.next(), segments.next()),
+        (Some("error"), Some(type_name), Some(slug), None)
+            if is_kebab_case(type_name) && is_kebab_case(slug)
+    )
+}
@@ -1,4 +1,5 @@
 use crate::generate_fields::generate_fields;
+use crate::type_name::generate_type_name;
 use crate::{ENTITY_ID_ATTR, FIELD_ATTR};
 use proc_macro::TokenStream;
 use syn::{Data, DeriveInput, Field};
@@ -6,6 +7,7 @@ use syn::{Data, DeriveInput, Field};
 pub fn generate_entity(ast: DeriveInput) -> TokenStream {
     let identity = ast.ident;
     let generics = ast.generics;
+    let type_name = generate_type_name(&identity, &generics, &ast.attrs);
     let fields: Vec<Field> = match ast.data {
         Data::Struct(data) => data.fields.into_iter().collect(),
         _ => panic!("Not a struct"),
@@ -38,6 +40,7 @@ pub fn generate_entity(ast: DeriveInput) -> TokenStream {
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     quote::quote!(
+        #type_name
 
         impl #impl_generics kern::building_blocks::entity::Entity #ty_generics for #identity #where_clause {
             type Id = #id_field_type;
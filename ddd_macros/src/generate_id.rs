@@ -0,0 +1,172 @@
+use quote::quote;
+use syn::{Attribute, Ident, Member, Token, Type, parse::ParseStream};
+
+use crate::new_type::{generate_display, generate_from_str, generate_schema, generate_serde};
+
+/// The `#[generate_id(Type, ...)]` attribute of the id field of an Aggregate
+struct GenerateId {
+    /// The type of the wrapped value
+    ty: Type,
+    /// `display`, implements Display
+    display: bool,
+    /// `from_str`, implements FromStr and `TryFrom<&str>`
+    from_str: bool,
+    /// `serde`, implements transparent Serialize and Deserialize
+    serde: bool,
+    /// `schema`, implements the utoipa schema of the wrapped value
+    schema: bool,
+    /// `v7`, adds `new_random`, which creates a time-ordered UUIDv7
+    v7: bool,
+    /// `ord`, derives PartialOrd and Ord
+    ord: bool,
+}
+
+/// Generates the id type of an Aggregate from its `#[generate_id(Type, ...)]` attribute
+/// # Arguments
+/// * `aggregate` - The Aggregate
+/// * `attrs` - The attributes of the Aggregate
+/// * `attribute` - The `generate_id` attribute
+pub fn generate_id(
+    aggregate: &Ident,
+    attrs: &[Attribute],
+    attribute: &Attribute,
+) -> proc_macro2::TokenStream {
+    let generate_id = match attribute.parse_args_with(parse_generate_id) {
+        Ok(generate_id) => generate_id,
+        Err(err) => return err.to_compile_error(),
+    };
+    let ty = &generate_id.ty;
+    let identity = Ident::new(&format!("{aggregate}Id"), aggregate.span());
+    let member = Member::Unnamed(0.into());
+    let checked = quote!(Ok(Self::new(value)));
+
+    let ord = match generate_id.ord {
+        true => quote!(#[derive(PartialOrd, Ord)]),
+        false => quote!(),
+    };
+    let new_random = match generate_id.v7 {
+        true => quote! {
+            /// Creates a new, time-ordered id
+            pub fn new_random() -> Self {
+                Self(kern::uuid::Uuid::now_v7())
+            }
+        },
+        false => quote!(),
+    };
+    let display = match generate_id.display {
+        true => generate_display(&identity, &member),
+        false => quote!(),
+    };
+    let from_str = match generate_id.from_str {
+        true => {
+            let error_prefix = match crate::type_name::type_error_prefix(aggregate, attrs) {
+                Ok(error_prefix) => error_prefix,
+                Err(err) => return err.to_compile_error(),
+            };
+            let error_key = format!("error.{error_prefix}.invalid-id");
+            let from_str = generate_from_str(&identity, ty, quote!(#error_key), &checked);
+            let registration = crate::declare_errors::register_generated_key(
+                &error_key,
+                "The id is invalid",
+                &identity.to_string(),
+            );
+            quote! {
+                #from_str
+
+                #registration
+
+                impl TryFrom<&str> for #identity {
+                    type Error = kern::building_blocks::error::domain_error::DomainError;
+
+                    fn try_from(value: &str) -> Result<Self, Self::Error> {
+                        value.parse()
+                    }
+                }
+            }
+        }
+        false => quote!(),
+    };
+    let serde = match generate_id.serde {
+        true => generate_serde(&identity, ty, &member, &checked),
+        false => quote!(),
+    };
+    let schema = match generate_id.schema {
+        true => generate_schema(&identity, ty),
+        false => quote!(),
+    };
+
+    quote!(
+        // Generate the ID struct of the struct
+        #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+        #ord
+        pub struct #identity(#ty);
+
+        impl #identity {
+            pub fn new(id: #ty) -> Self {
+                Self(id)
+            }
+
+            #new_random
+
+            pub fn value_as_ref(&self) -> &#ty {
+                &self.0
+            }
+
+            pub fn value(self) -> #ty {
+                self.0
+            }
+        }
+
+        impl From<#ty> for #identity {
+            fn from(value: #ty) -> Self {
+                Self(value)
+            }
+        }
+
+        impl kern::building_blocks::ids::AggregateId for #identity {}
+
+        #display
+
+        #from_str
+
+        #serde
+
+        #schema
+    )
+}
+
+/// Parses the type and the options of `#[generate_id(Type, ...)]`
+fn parse_generate_id(input: ParseStream) -> syn::Result<GenerateId> {
+    let mut generate_id = GenerateId {
+        ty: input.parse()?,
+        display: false,
+        from_str: false,
+        serde: false,
+        schema: false,
+        v7: false,
+        ord: false,
+    };
+    while !input.is_empty() {
+        input.parse::<Token![,]>()?;
+        if input.is_empty() {
+            break;
+        }
+        let option: Ident = input.parse()?;
+        let enabled = match option.to_string().as_str() {
+            "display" => &mut generate_id.display,
+            "from_str" => &mut generate_id.from_str,
+            "serde" => &mut generate_id.serde,
+            "schema" => &mut generate_id.schema,
+            "v7" => &mut generate_id.v7,
+            "ord" => &mut generate_id.ord,
+            _ => {
+                return Err(syn::Error::new(
+                    option.span(),
+                    "expected `display`, `from_str`, `serde`, `schema`, `v7` or `ord`",
+                ));
+            }
+        };
+        *enabled = true;
+    }
+    Ok(generate_id)
+}
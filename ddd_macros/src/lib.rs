@@ -2,10 +2,16 @@ use proc_macro::TokenStream;
 use syn::DeriveInput;
 
 mod aggregate;
+mod declare_errors;
 mod domain_event;
 mod entity;
 mod generate_fields;
+mod generate_id;
+mod new_type;
 mod request;
+mod requires_roles;
+mod try_new;
+mod type_name;
 mod value_object;
 
 /// Generates the required methods for the Aggregate struct
@@ -14,7 +20,17 @@ mod value_object;
 /// in the domain-driven design context
 ///
 /// Add the `field` attributes to the properties you want to generate getters for
-#[proc_macro_derive(Aggregate, attributes(generate_id, entity_id, field))]
+///
+/// Add the `pending_events` attribute to a `PendingEvents` property to implement `RecordsEvents`
+///
+/// Add `#[generate_id(Type)]` to the `id` field to generate its `<Aggregate>Id` type. The options
+/// `#[generate_id(Type, display, from_str, serde, schema, v7, ord)]` add `Display`, `FromStr` and
+/// `TryFrom<&str>` with an `error.<aggregate>.invalid-id` ErrorDetail, transparent serde, the
+/// utoipa schema of the wrapped value, a `new_random` constructor of a UUIDv7, and `Ord`
+#[proc_macro_derive(
+    Aggregate,
+    attributes(generate_id, entity_id, error_prefix, field, pending_events)
+)]
 pub fn aggregate_macro(item: TokenStream) -> TokenStream {
     // parse
     let ast: DeriveInput = syn::parse_macro_input!(item as DeriveInput);
@@ -28,7 +44,7 @@ pub fn aggregate_macro(item: TokenStream) -> TokenStream {
 /// Add the `field` attributes to the properties you want to generate getters for
 ///
 /// Make sure to import kern::Entity and kern:traits::entity::Entity
-#[proc_macro_derive(Entity, attributes(entity_id, field))]
+#[proc_macro_derive(Entity, attributes(entity_id, error_prefix, field))]
 pub fn entity_macro(item: TokenStream) -> TokenStream {
     // parse
     let ast: DeriveInput = syn::parse_macro_input!(item as DeriveInput);
@@ -41,8 +57,18 @@ pub fn entity_macro(item: TokenStream) -> TokenStream {
 ///
 /// Add the `field` attributes to the properties you want to generate getters for
 ///
+/// Add the `vo` attribute to generate a `try_new` constructor that checks the fields before it
+/// creates the value object and returns a `DomainError` with an `error.<type>.invalid-<field>`
+/// ErrorDetail for every failed check. The checks of a field are `non_empty`, `min_len = n`,
+/// `max_len = n`, `range(a..=b)` and `validate = "path::to::fn"`, where the function takes a
+/// reference to the field and returns `Result<(), DomainError>`. A failed range check has the
+/// `min` and `max` params, or `exclusive_max` for a half-open range such as `0..10`. The type only
+/// takes `validate = "path::to::fn"`, whose function takes a reference to the created value
+/// object. It only runs once every field check passed, so its ErrorDetails are never returned
+/// together with those of the fields
+///
 /// Make sure to import kern::ValueObject and kern:traits::value_object::ValueObject
-#[proc_macro_derive(ValueObject, attributes(field))]
+#[proc_macro_derive(ValueObject, attributes(error_prefix, field, vo))]
 pub fn value_object_macro(item: TokenStream) -> TokenStream {
     // parse
     let ast: DeriveInput = syn::parse_macro_input!(item as DeriveInput);
@@ -50,6 +76,27 @@ pub fn value_object_macro(item: TokenStream) -> TokenStream {
     value_object::generate_value_object(ast)
 }
 
+/// Generates the boilerplate code of a value object that wraps a single value, e.g.
+/// `struct Role(String)`
+///
+/// Generates `new`, `value`, `into_inner`, `From`, `AsRef`, `Borrow`, `Display`, `FromStr` and
+/// transparent serde implementations, and implements `ValueObject` and `TypeName`. Derive `Clone`,
+/// `PartialEq`, `Eq`, `Hash` and `Debug` as usual
+///
+/// The `vo` checks of the ValueObject derive can be added to the type or to its field, and they
+/// check the wrapped value. With checks `new` returns a `DomainError` with an
+/// `error.<type>.invalid` ErrorDetail for every failed check, `From` becomes `TryFrom` and
+/// deserialization fails for an invalid value. `#[new_type(...)]` takes `schema` to implement the
+/// utoipa schema of the wrapped value, and `no_display`, `no_from_str` and `no_serde` to skip the
+/// implementations the wrapped type does not support
+#[proc_macro_derive(NewType, attributes(error_prefix, new_type, vo))]
+pub fn new_type_macro(item: TokenStream) -> TokenStream {
+    // parse
+    let ast: DeriveInput = syn::parse_macro_input!(item as DeriveInput);
+    // generate
+    new_type::generate_new_type(ast)
+}
+
 /// Generates the boilerplate code for a DomainEvent
 #[proc_macro_derive(DomainEvent)]
 pub fn domain_event_macro(item: TokenStream) -> TokenStream {
@@ -60,7 +107,7 @@ pub fn domain_event_macro(item: TokenStream) -> TokenStream {
 }
 
 /// Generates the boilerplate code for a Request
-#[proc_macro_derive(Request)]
+#[proc_macro_derive(Request, attributes(error_prefix))]
 pub fn request_macro(item: TokenStream) -> TokenStream {
     // parse
     let ast: DeriveInput = syn::parse_macro_input!(item as DeriveInput);
@@ -69,7 +116,7 @@ pub fn request_macro(item: TokenStream) -> TokenStream {
 }
 
 /// Generates the boilerplate code for an AutheitcatedRequest
-#[proc_macro_derive(AuthenticatedRequest)]
+#[proc_macro_derive(AuthenticatedRequest, attributes(error_prefix))]
 pub fn authenticated_request_macro(item: TokenStream) -> TokenStream {
     // parse
     let ast: DeriveInput = syn::parse_macro_input!(item as DeriveInput);
@@ -77,6 +124,47 @@ pub fn authenticated_request_macro(item: TokenStream) -> TokenStream {
     request::generate_authenticated_request(ast)
 }
 
+/// Guards an `impl UseCase` block with role requirements on its `AuthenticatedRequest`
+///
+/// `any("admin", "owner")` requires at least one of the roles and `all("admin", "owner")` requires
+/// every one of them. The requirements are checked before the body of `handle` runs, and a request
+/// that does not satisfy them is answered with a `ForbiddenError` with the key
+/// `error.<use-case>.forbidden`. The use case name defaults to the kebab case type name without the
+/// `UseCase` suffix and can be set with `use_case = "name"`
+///
+/// The Response of the UseCase must be a `Result` whose error implements `From<ForbiddenError>`.
+/// Place the attribute above `#[async_trait]`
+#[proc_macro_attribute]
+pub fn requires_roles(args: TokenStream, item: TokenStream) -> TokenStream {
+    // parse
+    let item = syn::parse_macro_input!(item as syn::ItemImpl);
+    // generate
+    requires_roles::generate_requires_roles(args, item)
+}
+
+/// Declares ErrorDetail constants and registers them in the `ErrorCatalog`
+///
+/// Every declaration has the form `pub NAME = "error.<type>.<slug>" => "message";`, where the type
+/// and the slug are lowercase kebab case. A malformed key fails to compile, and so does a key that
+/// is declared twice in one invocation.
+///
+/// The constants are registered with `linkme` only if the `error_catalog` feature of kern is
+/// enabled. Independent of the feature, every key exports an unmangled `__kern_error_key.<key>`
+/// symbol, so a key that is declared twice in a crate fails to compile and a key that is declared
+/// in two crates fails to link. This is checked when the binary is linked, not when a library is
+/// compiled, and it has two consequences:
+/// * A program that links two versions of a crate that declares errors, e.g. through two versions
+///   of one dependency, fails to link with a duplicate symbol error
+/// * The symbols are exported by a `cdylib`, so two such libraries loaded into one process may
+///   clash, and a crate that declares errors adds one exported symbol per key to the library
+#[proc_macro]
+pub fn declare_errors(item: TokenStream) -> TokenStream {
+    // parse
+    let declarations = syn::parse_macro_input!(item as declare_errors::ErrorDeclarations);
+    // generate
+    declare_errors::generate_declare_errors(declarations)
+}
+
 /// Turns a string into snake case
 fn to_snake_case(name: String) -> String {
     let mut snake_case = String::new();
@@ -100,3 +188,7 @@ fn to_snake_case(name: String) -> String {
 
 const FIELD_ATTR: &str = "field";
 const ENTITY_ID_ATTR: &str = "entity_id";
+const PENDING_EVENTS_ATTR: &str = "pending_events";
+const ERROR_PREFIX_ATTR: &str = "error_prefix";
+const VO_ATTR: &str = "vo";
+const NEW_TYPE_ATTR: &str = "new_type";
@@ -0,0 +1,312 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Fields, Ident, Member, Type, spanned::Spanned};
+
+use crate::NEW_TYPE_ATTR;
+use crate::try_new::{checks, generate_check};
+use crate::type_name::generate_type_name;
+
+/// The options of the `#[new_type(...)]` attribute
+#[derive(Default)]
+struct Options {
+    /// `schema`, implements the utoipa schema of the value
+    schema: bool,
+    /// `no_display`, skips the Display implementation
+    no_display: bool,
+    /// `no_from_str`, skips the FromStr implementation
+    no_from_str: bool,
+    /// `no_serde`, skips the Serialize and Deserialize implementations
+    no_serde: bool,
+}
+
+pub fn generate_new_type(ast: DeriveInput) -> TokenStream {
+    generate(&ast)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn generate(ast: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    if !ast.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            ast.generics.span(),
+            "NewType can not be derived for generic types",
+        ));
+    }
+    let field = match &ast.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) if fields.named.len() == 1 => fields.named.first(),
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => fields.unnamed.first(),
+            _ => None,
+        },
+        _ => None,
+    }
+    .ok_or_else(|| {
+        syn::Error::new(
+            ast.ident.span(),
+            "NewType can only be derived for structs with a single field",
+        )
+    })?;
+
+    let identity = &ast.ident;
+    let ty = &field.ty;
+    let member = match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(0.into()),
+    };
+    let options = options(&ast.attrs)?;
+    let type_name = generate_type_name(identity, &ast.generics, &ast.attrs);
+
+    let mut value_checks = checks(&ast.attrs)?;
+    value_checks.extend(checks(&field.attrs)?);
+    let fallible = !value_checks.is_empty();
+    let value_checks = value_checks
+        .iter()
+        .map(|check| generate_check(identity, &ast.generics, quote!(value), None, check));
+
+    let constructor = match fallible {
+        true => quote! {
+            /// Creates the value if it satisfies its `#[vo(...)]` checks, otherwise returns the
+            /// ErrorDetails of every failed check
+            /// # Arguments
+            /// * `value` - The value
+            pub fn new(value: #ty) -> Result<Self, kern::building_blocks::error::domain_error::DomainError> {
+                let mut __kern_validation = kern::building_blocks::error::validation::ValidationBuilder::new();
+                #(#value_checks)*
+                __kern_validation.build(|| Self { #member: value })
+            }
+        },
+        false => quote! {
+            /// Creates the value
+            /// # Arguments
+            /// * `value` - The value
+            pub fn new(value: #ty) -> Self {
+                Self { #member: value }
+            }
+        },
+    };
+    let from = match fallible {
+        true => quote! {
+            impl TryFrom<#ty> for #identity {
+                type Error = kern::building_blocks::error::domain_error::DomainError;
+
+                fn try_from(value: #ty) -> Result<Self, Self::Error> {
+                    Self::new(value)
+                }
+            }
+        },
+        false => quote! {
+            impl From<#ty> for #identity {
+                fn from(value: #ty) -> Self {
+                    Self::new(value)
+                }
+            }
+        },
+    };
+    let checked = match fallible {
+        true => quote!(Self::new(value)),
+        false => quote!(Ok(Self::new(value))),
+    };
+
+    let display = match options.no_display {
+        true => quote!(),
+        false => generate_display(identity, &member),
+    };
+    let from_str = match options.no_from_str {
+        true => quote!(),
+        false => {
+            let error_key = quote! {
+                format!(
+                    "error.{}.invalid",
+                    <Self as kern::building_blocks::type_name::TypeName>::error_prefix()
+                )
+            };
+            generate_from_str(identity, ty, error_key, &checked)
+        }
+    };
+    let serde = match options.no_serde {
+        true => quote!(),
+        false => generate_serde(identity, ty, &member, &checked),
+    };
+    let schema = match options.schema {
+        true => generate_schema(identity, ty),
+        false => quote!(),
+    };
+
+    Ok(quote! {
+        impl #identity {
+            #constructor
+
+            /// The value
+            pub fn value(&self) -> &#ty {
+                &self.#member
+            }
+
+            /// Consumes the value object and returns its value
+            pub fn into_inner(self) -> #ty {
+                self.#member
+            }
+        }
+
+        impl kern::building_blocks::value_object::ValueObject for #identity {}
+
+        #type_name
+
+        #from
+
+        impl AsRef<#ty> for #identity {
+            fn as_ref(&self) -> &#ty {
+                &self.#member
+            }
+        }
+
+        impl std::borrow::Borrow<#ty> for #identity {
+            fn borrow(&self) -> &#ty {
+                &self.#member
+            }
+        }
+
+        #display
+
+        #from_str
+
+        #serde
+
+        #schema
+    })
+}
+
+/// Generates the Display implementation that displays the wrapped value
+/// # Arguments
+/// * `identity` - The wrapping type
+/// * `member` - The field of the wrapped value
+pub(crate) fn generate_display(identity: &Ident, member: &Member) -> proc_macro2::TokenStream {
+    quote! {
+        impl std::fmt::Display for #identity {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Display::fmt(&self.#member, f)
+            }
+        }
+    }
+}
+
+/// Generates the FromStr implementation that parses the wrapped value. A value that can not be
+/// parsed is a DomainError with the error key and the `format` code
+/// # Arguments
+/// * `identity` - The wrapping type
+/// * `ty` - The type of the wrapped value
+/// * `error_key` - The expression of the error key
+/// * `checked` - The expression that creates the `Result` of the wrapping type from `value`
+pub(crate) fn generate_from_str(
+    identity: &Ident,
+    ty: &Type,
+    error_key: proc_macro2::TokenStream,
+    checked: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {
+        impl std::str::FromStr for #identity {
+            type Err = kern::building_blocks::error::domain_error::DomainError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                let value = <#ty as std::str::FromStr>::from_str(value).map_err(|err| {
+                    kern::building_blocks::error::error_detail::ErrorDetail::new(#error_key, err.to_string())
+                        .with_param("code", "format")
+                })?;
+                #checked
+            }
+        }
+    }
+}
+
+/// Generates the transparent Serialize and Deserialize implementations of the wrapped value
+/// # Arguments
+/// * `identity` - The wrapping type
+/// * `ty` - The type of the wrapped value
+/// * `member` - The field of the wrapped value
+/// * `checked` - The expression that creates the `Result` of the wrapping type from `value`
+pub(crate) fn generate_serde(
+    identity: &Ident,
+    ty: &Type,
+    member: &Member,
+    checked: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {
+        impl kern::serde::Serialize for #identity {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: kern::serde::Serializer,
+            {
+                kern::serde::Serialize::serialize(&self.#member, serializer)
+            }
+        }
+
+        impl<'de> kern::serde::Deserialize<'de> for #identity {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: kern::serde::Deserializer<'de>,
+            {
+                let value = <#ty as kern::serde::Deserialize<'de>>::deserialize(deserializer)?;
+                let value: Result<Self, kern::building_blocks::error::domain_error::DomainError> = #checked;
+                value.map_err(kern::serde::de::Error::custom)
+            }
+        }
+    }
+}
+
+/// Generates the utoipa schema implementations, which document the wrapped value. utoipa has no
+/// schema of a Uuid, so it is documented as a string with the uuid format
+/// # Arguments
+/// * `identity` - The wrapping type
+/// * `ty` - The type of the wrapped value
+pub(crate) fn generate_schema(identity: &Ident, ty: &Type) -> proc_macro2::TokenStream {
+    let is_uuid = matches!(
+        ty,
+        Type::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "Uuid")
+    );
+    let schema = match is_uuid {
+        true => quote! {
+            kern::utoipa::openapi::schema::ObjectBuilder::new()
+                .schema_type(kern::utoipa::openapi::schema::Type::String)
+                .format(Some(kern::utoipa::openapi::schema::SchemaFormat::KnownFormat(
+                    kern::utoipa::openapi::schema::KnownFormat::Uuid,
+                )))
+                .into()
+        },
+        false => quote!(<#ty as kern::utoipa::PartialSchema>::schema()),
+    };
+    quote! {
+        impl kern::utoipa::PartialSchema for #identity {
+            fn schema() -> kern::utoipa::openapi::RefOr<kern::utoipa::openapi::schema::Schema> {
+                #schema
+            }
+        }
+
+        impl kern::utoipa::ToSchema for #identity {}
+    }
+}
+
+/// Reads the options of the `#[new_type(...)]` attributes
+fn options(attrs: &[Attribute]) -> syn::Result<Options> {
+    let mut options = Options::default();
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident(NEW_TYPE_ATTR))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("schema") {
+                options.schema = true;
+            } else if meta.path.is_ident("no_display") {
+                options.no_display = true;
+            } else if meta.path.is_ident("no_from_str") {
+                options.no_from_str = true;
+            } else if meta.path.is_ident("no_serde") {
+                options.no_serde = true;
+            } else {
+                return Err(
+                    meta.error("expected `schema`, `no_display`, `no_from_str` or `no_serde`")
+                );
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
@@ -1,9 +1,21 @@
 use proc_macro::TokenStream;
 use syn::{Data, DeriveInput, Field};
 
+use crate::type_name::generate_type_name;
+
 pub fn generate_request(ast: DeriveInput) -> TokenStream {
+    generate_request_tokens(ast, true).into()
+}
+
+/// Generates the Request implementation
+/// # Arguments
+/// * `ast` - The request struct
+/// * `with_from_context` - Whether the `from_context` constructor of a Request is generated, an
+///   AuthenticatedRequest generates its own
+fn generate_request_tokens(ast: DeriveInput, with_from_context: bool) -> proc_macro2::TokenStream {
     let identity = ast.ident;
     let generics = ast.generics;
+    let type_name = generate_type_name(&identity, &generics, &ast.attrs);
     let fields: Vec<Field> = match ast.data {
         Data::Struct(data) => data.fields.into_iter().collect(),
         _ => panic!("Not a struct"),
@@ -32,7 +44,46 @@ pub fn generate_request(ast: DeriveInput) -> TokenStream {
         .expect("No 'issued_at' field found.");
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let from_context = match with_from_context {
+        true => {
+            // The remaining fields are the payload of the request, passed to `from_context` in order
+            let payload_fields: Vec<&Field> = fields
+                .iter()
+                .filter(|field| {
+                    !REQUEST_CONTEXT_FIELDS
+                        .contains(&field.ident.as_ref().unwrap().to_string().as_str())
+                })
+                .collect();
+            let payload_names: Vec<_> = payload_fields.iter().map(|field| &field.ident).collect();
+            let payload_types: Vec<_> = payload_fields.iter().map(|field| &field.ty).collect();
+            quote::quote!(
+                impl #impl_generics #identity #ty_generics #where_clause {
+                    /// Creates the request from the RequestContext and the remaining fields of the
+                    /// request. The user of the RequestContext is not part of the request
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn from_context<__KernUserId>(
+                        context: kern::application::request_context::RequestContext<__KernUserId, #authorized_party_type>,
+                        #(#payload_names: #payload_types),*
+                    ) -> Self {
+                        Self {
+                            request_id: context.request_id,
+                            authorized_party: context.authorized_party,
+                            environment: context.environment,
+                            issued_at: context.issued_at,
+                            #(#payload_names),*
+                        }
+                    }
+                }
+            )
+        }
+        false => quote::quote!(),
+    };
+
     quote::quote!(
+        #type_name
+
+        #from_context
 
         impl #impl_generics kern::application::request::Request #ty_generics for #identity #where_clause {
             type RequestId = kern::application::ids::RequestId;
@@ -55,7 +106,6 @@ pub fn generate_request(ast: DeriveInput) -> TokenStream {
             }
         }
     )
-    .into()
 }
 
 pub fn generate_authenticated_request(ast: DeriveInput) -> TokenStream {
@@ -79,7 +129,23 @@ pub fn generate_authenticated_request(ast: DeriveInput) -> TokenStream {
         .find(|field| field.ident.as_ref().unwrap() == "roles")
         .expect("No 'roles' field found.");
 
-    let request_token_stream: proc_macro2::TokenStream = generate_request(ast_clone).into();
+    let authorized_party_type = &fields
+        .iter()
+        .find(|field| field.ident.as_ref().unwrap() == "authorized_party")
+        .expect("No 'authorized_party' field found.")
+        .ty;
+
+    // The remaining fields are the payload of the request, passed to `from_context` in order
+    let payload_fields: Vec<&Field> = fields
+        .iter()
+        .filter(|field| {
+            !CONTEXT_FIELDS.contains(&field.ident.as_ref().unwrap().to_string().as_str())
+        })
+        .collect();
+    let payload_names: Vec<_> = payload_fields.iter().map(|field| &field.ident).collect();
+    let payload_types: Vec<_> = payload_fields.iter().map(|field| &field.ty).collect();
+
+    let request_token_stream = generate_request_tokens(ast_clone, false);
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     quote::quote!(
@@ -98,6 +164,39 @@ pub fn generate_authenticated_request(ast: DeriveInput) -> TokenStream {
                 &self.roles
             }
         }
+
+        impl #impl_generics #identity #ty_generics #where_clause {
+            /// Creates the request from the RequestContext and the remaining fields of the request
+            #[allow(clippy::too_many_arguments)]
+            pub fn from_context(
+                context: kern::application::request_context::RequestContext<#user_id_type, #authorized_party_type>,
+                #(#payload_names: #payload_types),*
+            ) -> Self {
+                Self {
+                    request_id: context.request_id,
+                    authorized_party: context.authorized_party,
+                    environment: context.environment,
+                    issued_at: context.issued_at,
+                    user_id: context.user_id,
+                    roles: context.roles,
+                    #(#payload_names),*
+                }
+            }
+        }
     )
     .into()
 }
+
+/// The fields of a Request that are filled from the RequestContext
+const REQUEST_CONTEXT_FIELDS: [&str; 4] =
+    ["request_id", "authorized_party", "environment", "issued_at"];
+
+/// The fields of an AuthenticatedRequest that are filled from the RequestContext
+const CONTEXT_FIELDS: [&str; 6] = [
+    "request_id",
+    "authorized_party",
+    "environment",
+    "issued_at",
+    "user_id",
+    "roles",
+];
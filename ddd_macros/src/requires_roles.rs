@@ -0,0 +1,192 @@
+use proc_macro::TokenStream;
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::{
+    Expr, ExprLit, FnArg, ImplItem, ImplItemType, ItemImpl, Lit, LitStr, Meta, Token, Type,
+    parse::Parser, punctuated::Punctuated, spanned::Spanned,
+};
+
+/// A role requirement of the attribute
+enum Requirement {
+    /// The user must have at least one of the roles
+    Any(Vec<LitStr>),
+    /// The user must have every one of the roles
+    All(Vec<LitStr>),
+}
+
+pub fn generate_requires_roles(args: TokenStream, item: ItemImpl) -> TokenStream {
+    match expand(args, item) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(args: TokenStream, mut item: ItemImpl) -> syn::Result<proc_macro2::TokenStream> {
+    let metas = Punctuated::<Meta, Token![,]>::parse_terminated.parse(args)?;
+
+    let mut requirements = Vec::new();
+    let mut use_case = None;
+    for meta in metas {
+        match &meta {
+            Meta::List(list) if list.path.is_ident("any") || list.path.is_ident("all") => {
+                let roles: Vec<LitStr> = list
+                    .parse_args_with(Punctuated::<LitStr, Token![,]>::parse_terminated)?
+                    .into_iter()
+                    .collect();
+                if roles.is_empty() {
+                    return Err(syn::Error::new(list.span(), "expected at least one role"));
+                }
+                requirements.push(if list.path.is_ident("any") {
+                    Requirement::Any(roles)
+                } else {
+                    Requirement::All(roles)
+                });
+            }
+            Meta::NameValue(name_value) if name_value.path.is_ident("use_case") => {
+                match &name_value.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(name),
+                        ..
+                    }) => use_case = Some(name.value()),
+                    value => {
+                        return Err(syn::Error::new(value.span(), "expected a string literal"));
+                    }
+                }
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    meta.span(),
+                    "expected `any(\"role\", ..)`, `all(\"role\", ..)` or `use_case = \"name\"`",
+                ));
+            }
+        }
+    }
+    if requirements.is_empty() {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "expected `any(\"role\", ..)` or `all(\"role\", ..)`",
+        ));
+    }
+
+    let use_case = match use_case {
+        Some(use_case) => use_case,
+        None => default_use_case_name(&item.self_ty)?,
+    };
+
+    let request_type = item
+        .items
+        .iter()
+        .find_map(|item| match item {
+            ImplItem::Type(ImplItemType { ident, ty, .. }) if ident == "Request" => Some(ty),
+            _ => None,
+        })
+        .cloned()
+        .ok_or_else(|| {
+            syn::Error::new(
+                item.self_ty.span(),
+                "#[requires_roles] must be placed on an `impl UseCase` block that defines `type Request`",
+            )
+        })?;
+
+    let handle = item
+        .items
+        .iter_mut()
+        .find_map(|item| match item {
+            ImplItem::Fn(function) if function.sig.ident == "handle" => Some(function),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            syn::Error::new(
+                item.self_ty.span(),
+                "#[requires_roles] must be placed on an `impl UseCase` block that defines `handle`",
+            )
+        })?;
+    if handle.sig.asyncness.is_none() {
+        return Err(syn::Error::new(
+            handle.sig.span(),
+            "expected `async fn handle`, place #[requires_roles] above #[async_trait]",
+        ));
+    }
+
+    // The request argument is renamed, so the original pattern of the argument can still be used
+    let request = format_ident!("__kern_request");
+    let request_pattern = match handle.sig.inputs.iter_mut().nth(1) {
+        Some(FnArg::Typed(argument)) => {
+            std::mem::replace(argument.pat.as_mut(), syn::parse_quote!(#request))
+        }
+        _ => {
+            return Err(syn::Error::new(
+                handle.sig.span(),
+                "expected `handle(&self, request: Self::Request)`",
+            ));
+        }
+    };
+
+    let policies = requirements.iter().map(|requirement| {
+        let (policy, roles) = match requirement {
+            Requirement::Any(roles) => (quote!(RequiresAnyRole), roles),
+            Requirement::All(roles) => (quote!(RequiresAllRoles), roles),
+        };
+        quote! {
+            kern::application::policy::UseCasePolicy::new(
+                #use_case,
+                kern::application::policy::#policy::new([#(#roles),*]),
+            )
+            .authorize(&#request)?;
+        }
+    });
+
+    let block = &handle.block;
+    handle.block = syn::parse_quote!({
+        #(#policies)*
+        let #request_pattern = #request;
+        #block
+    });
+
+    let assertion = authenticated_request_assertion(&request_type);
+    let registration = crate::declare_errors::register_generated_key(
+        &format!("error.{use_case}.forbidden"),
+        &format!("Not allowed to execute {use_case}"),
+        &item.self_ty.to_token_stream().to_string(),
+    );
+    Ok(quote! {
+        #assertion
+
+        #registration
+
+        #item
+    })
+}
+
+/// Fails to compile with an error on the request type if it does not implement
+/// `AuthenticatedRequest`
+fn authenticated_request_assertion(request_type: &Type) -> proc_macro2::TokenStream {
+    quote_spanned! {request_type.span()=>
+        const _: fn() = || {
+            fn requires_roles_needs_an_authenticated_request<R>()
+            where
+                R: kern::application::request::AuthenticatedRequest,
+            {
+            }
+            requires_roles_needs_an_authenticated_request::<#request_type>();
+        };
+    }
+}
+
+/// Turns the name of the UseCase type into the kebab case name that is used in the error key,
+/// without the `UseCase` suffix
+fn default_use_case_name(self_ty: &Type) -> syn::Result<String> {
+    let Type::Path(path) = self_ty else {
+        return Err(syn::Error::new(
+            self_ty.span(),
+            "expected a named type, or set the name with `use_case = \"name\"`",
+        ));
+    };
+    let name = path
+        .path
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+        .unwrap_or_default();
+    let name = name.strip_suffix("UseCase").unwrap_or(&name).to_string();
+    Ok(super::to_snake_case(name).replace('_', "-"))
+}
@@ -0,0 +1,255 @@
+use quote::quote;
+use syn::{
+    Attribute, Data, DeriveInput, Expr, Fields, Generics, Ident, LitInt, LitStr, Path, RangeLimits,
+    spanned::Spanned,
+};
+
+use crate::VO_ATTR;
+
+/// A check of a `#[vo(...)]` attribute
+pub(crate) enum Check {
+    /// `non_empty`
+    NonEmpty,
+    /// `min_len = 1`
+    MinLen(LitInt),
+    /// `max_len = 64`
+    MaxLen(LitInt),
+    /// `range(0..=100)`
+    Range(Expr),
+    /// `validate = "path::to::fn"`
+    Validate(Path),
+}
+
+/// Generates `try_new`, which checks the `#[vo(...)]` attributes of the fields and of the type
+/// before it creates the ValueObject. The checks of the fields are accumulated, the validation of
+/// the type takes the created ValueObject, so it only runs once every field check passed. Nothing
+/// is generated if there are no `#[vo(...)]` attributes
+pub fn generate_try_new(ast: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let type_checks = checks(&ast.attrs)?;
+    if let Some(check) = type_checks
+        .iter()
+        .find(|check| !matches!(check, Check::Validate(_)))
+    {
+        return Err(syn::Error::new(
+            check_span(check),
+            "only `validate = \"path::to::fn\"` can be used on the type",
+        ));
+    }
+
+    let Data::Struct(data) = &ast.data else {
+        return match type_checks.is_empty() {
+            true => Ok(quote!()),
+            false => Err(syn::Error::new(
+                ast.ident.span(),
+                "#[vo(...)] can only be used on structs with named fields",
+            )),
+        };
+    };
+    let mut fields = Vec::new();
+    for field in data.fields.iter() {
+        fields.push((field, checks(&field.attrs)?));
+    }
+    if type_checks.is_empty() && fields.iter().all(|(_, checks)| checks.is_empty()) {
+        return Ok(quote!());
+    }
+    let Fields::Named(_) = &data.fields else {
+        return Err(syn::Error::new(
+            ast.ident.span(),
+            "#[vo(...)] can only be used on structs with named fields",
+        ));
+    };
+
+    let identity = &ast.ident;
+    let field_checks = fields.iter().flat_map(|(field, checks)| {
+        let ident = field.ident.as_ref().unwrap();
+        checks.iter().map(move |check| {
+            generate_check(identity, &ast.generics, quote!(#ident), Some(ident), check)
+        })
+    });
+    let type_checks = type_checks.iter().map(|check| match check {
+        Check::Validate(path) => quote!(#path(&__kern_value)?;),
+        _ => unreachable!(),
+    });
+
+    let params = fields.iter().map(|(field, _)| {
+        let ident = &field.ident;
+        let ty = &field.ty;
+        quote!(#ident: #ty)
+    });
+    let idents = fields.iter().map(|(field, _)| &field.ident);
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #identity #ty_generics #where_clause {
+            /// Creates the ValueObject if it satisfies its `#[vo(...)]` checks, otherwise returns
+            /// the ErrorDetails of every failed field check. The validation of the type only runs
+            /// once the fields are valid
+            #[allow(clippy::too_many_arguments)]
+            pub fn try_new(#(#params),*) -> Result<Self, kern::building_blocks::error::domain_error::DomainError> {
+                let mut __kern_validation = kern::building_blocks::error::validation::ValidationBuilder::new();
+                #(#field_checks)*
+                let __kern_value = __kern_validation.build(|| Self { #(#idents),* })?;
+                #(#type_checks)*
+                Ok(__kern_value)
+            }
+        }
+    })
+}
+
+/// Generates the check of a value, which pushes its ErrorDetail to `__kern_validation`. The key
+/// of the ErrorDetail is `error.<prefix>.invalid-<field>` for a field and `error.<prefix>.invalid`
+/// for the whole value, e.g. the value of a NewType
+/// # Arguments
+/// * `identity` - The type that is validated
+/// * `generics` - The generics of the type
+/// * `value` - The expression of the checked value
+/// * `field` - The field of the checked value, if it is a field
+/// * `check` - The check
+pub(crate) fn generate_check(
+    identity: &Ident,
+    generics: &Generics,
+    value: proc_macro2::TokenStream,
+    field: Option<&Ident>,
+    check: &Check,
+) -> proc_macro2::TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let (suffix, label, pointer) = match field {
+        Some(field) => {
+            let field_name = field.to_string();
+            (
+                format!("invalid-{}", field_name.replace('_', "-")),
+                field_name.replace('_', " "),
+                Some(format!("/{field_name}")),
+            )
+        }
+        None => (
+            "invalid".to_string(),
+            super::to_snake_case(identity.to_string()).replace('_', " "),
+            None,
+        ),
+    };
+    let pointer = pointer.iter();
+    let error_detail = |message: proc_macro2::TokenStream, code: &str| {
+        quote! {{
+            let __kern_error_key = format!(
+                "error.{}.{}",
+                <#identity #ty_generics as kern::building_blocks::type_name::TypeName>::error_prefix(),
+                #suffix
+            );
+            kern::building_blocks::error::error_detail::ErrorDetail::new(__kern_error_key, #message)
+                #(.with_field(#pointer))*
+                .with_param("code", #code)
+        }}
+    };
+
+    match check {
+        Check::NonEmpty => {
+            let detail = error_detail(
+                quote!(concat!("The ", #label, " must not be empty")),
+                "length",
+            );
+            quote! {
+                if kern::building_blocks::value_object::Length::length(&#value) == 0 {
+                    __kern_validation.push(#detail.with_param("min", 1));
+                }
+            }
+        }
+        Check::MinLen(min) => {
+            let detail = error_detail(
+                quote!(concat!("The length of the ", #label, " must be at least ", #min)),
+                "length",
+            );
+            quote! {
+                if kern::building_blocks::value_object::Length::length(&#value) < #min {
+                    __kern_validation.push(#detail.with_param("min", #min));
+                }
+            }
+        }
+        Check::MaxLen(max) => {
+            let detail = error_detail(
+                quote!(concat!("The length of the ", #label, " must be at most ", #max)),
+                "length",
+            );
+            quote! {
+                if kern::building_blocks::value_object::Length::length(&#value) > #max {
+                    __kern_validation.push(#detail.with_param("max", #max));
+                }
+            }
+        }
+        Check::Range(range) => {
+            let detail = error_detail(
+                quote!(concat!("The ", #label, " must be in the range ", stringify!(#range))),
+                "range",
+            );
+            let bounds = match range {
+                Expr::Range(range) => {
+                    let min = range
+                        .start
+                        .iter()
+                        .map(|start| quote!(.with_param("min", #start)));
+                    // The end of a half-open range is not a valid value
+                    let max_param = match range.limits {
+                        RangeLimits::Closed(_) => "max",
+                        RangeLimits::HalfOpen(_) => "exclusive_max",
+                    };
+                    let max = range
+                        .end
+                        .iter()
+                        .map(|end| quote!(.with_param(#max_param, #end)));
+                    quote!(#(#min)* #(#max)*)
+                }
+                _ => quote!(),
+            };
+            quote! {
+                if !(#range).contains(&#value) {
+                    __kern_validation.push(#detail #bounds);
+                }
+            }
+        }
+        Check::Validate(path) => quote! {
+            __kern_validation.check(#path(&#value));
+        },
+    }
+}
+
+/// Reads the checks of the `#[vo(...)]` attributes
+pub(crate) fn checks(attrs: &[Attribute]) -> syn::Result<Vec<Check>> {
+    let mut checks = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident(VO_ATTR)) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("non_empty") {
+                checks.push(Check::NonEmpty);
+            } else if meta.path.is_ident("min_len") {
+                checks.push(Check::MinLen(meta.value()?.parse()?));
+            } else if meta.path.is_ident("max_len") {
+                checks.push(Check::MaxLen(meta.value()?.parse()?));
+            } else if meta.path.is_ident("range") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let range: Expr = content.parse()?;
+                if !matches!(range, Expr::Range(_)) {
+                    return Err(syn::Error::new(range.span(), "expected a range, e.g. `0..=100`"));
+                }
+                checks.push(Check::Range(range));
+            } else if meta.path.is_ident("validate") {
+                let path: LitStr = meta.value()?.parse()?;
+                checks.push(Check::Validate(path.parse()?));
+            } else {
+                return Err(meta.error(
+                    "expected `non_empty`, `min_len = n`, `max_len = n`, `range(a..=b)` or `validate = \"path::to::fn\"`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(checks)
+}
+
+fn check_span(check: &Check) -> proc_macro2::Span {
+    match check {
+        Check::NonEmpty => proc_macro2::Span::call_site(),
+        Check::MinLen(lit) | Check::MaxLen(lit) => lit.span(),
+        Check::Range(range) => range.span(),
+        Check::Validate(path) => path.span(),
+    }
+}
@@ -0,0 +1,78 @@
+use syn::{Attribute, Expr, ExprLit, Generics, Ident, Lit, Meta, spanned::Spanned};
+
+use crate::ERROR_PREFIX_ATTR;
+
+/// Generates the TypeName implementation of a type. The type name is the snake case name of the
+/// type and the error prefix is the kebab case name of the type, unless it is set with
+/// `#[error_prefix = "prefix"]`
+pub fn generate_type_name(
+    identity: &Ident,
+    generics: &Generics,
+    attrs: &[Attribute],
+) -> proc_macro2::TokenStream {
+    let type_name = super::to_snake_case(identity.to_string());
+    let error_prefix = match type_error_prefix(identity, attrs) {
+        Ok(error_prefix) => error_prefix,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote::quote!(
+        impl #impl_generics kern::building_blocks::type_name::TypeName for #identity #ty_generics #where_clause {
+            fn type_name() -> &'static str {
+                #type_name
+            }
+
+            fn error_prefix() -> &'static str {
+                #error_prefix
+            }
+        }
+    )
+}
+
+/// The error prefix of a type, which is set with `#[error_prefix = "prefix"]` or defaults to the
+/// kebab case name of the type
+pub(crate) fn type_error_prefix(identity: &Ident, attrs: &[Attribute]) -> syn::Result<String> {
+    Ok(error_prefix(attrs)?
+        .unwrap_or_else(|| super::to_snake_case(identity.to_string()).replace('_', "-")))
+}
+
+/// Reads the `#[error_prefix = "prefix"]` attribute
+pub(crate) fn error_prefix(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+    let Some(attr) = attrs
+        .iter()
+        .find(|attr| attr.path().is_ident(ERROR_PREFIX_ATTR))
+    else {
+        return Ok(None);
+    };
+    match &attr.meta {
+        Meta::NameValue(name_value) => match &name_value.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(prefix),
+                ..
+            }) => {
+                let value = prefix.value();
+                let is_kebab_case = !value.is_empty()
+                    && value.split('-').all(|word| {
+                        !word.is_empty()
+                            && word
+                                .chars()
+                                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+                    });
+                if is_kebab_case {
+                    Ok(Some(value))
+                } else {
+                    Err(syn::Error::new(
+                        prefix.span(),
+                        "expected a lowercase kebab case error prefix",
+                    ))
+                }
+            }
+            value => Err(syn::Error::new(value.span(), "expected a string literal")),
+        },
+        meta => Err(syn::Error::new(
+            meta.span(),
+            "expected `#[error_prefix = \"prefix\"]`",
+        )),
+    }
+}
@@ -1,18 +1,31 @@
 use crate::FIELD_ATTR;
 use crate::generate_fields::generate_fields;
+use crate::try_new::generate_try_new;
+use crate::type_name::generate_type_name;
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use syn::{Data, DataEnum, DataStruct, DeriveInput, Field, Fields, Generics};
 
 pub fn generate_value_object(ast: DeriveInput) -> TokenStream {
+    let try_new = generate_try_new(&ast).unwrap_or_else(|err| err.to_compile_error());
     let identity = ast.ident;
     let generics = ast.generics;
+    let type_name = generate_type_name(&identity, &generics, &ast.attrs);
 
-    match ast.data {
+    let value_object: proc_macro2::TokenStream = match ast.data {
         Data::Struct(data) => generate_value_object_for_struct(&identity, &generics, data),
         Data::Enum(data) => generate_value_object_for_enum(&identity, &generics, data),
         _ => panic!("Not a struct"),
     }
+    .into();
+    quote::quote!(
+        #value_object
+
+        #type_name
+
+        #try_new
+    )
+    .into()
 }
 
 pub fn generate_value_object_for_struct(
@@ -1,8 +1,16 @@
 pub mod application_event;
+pub mod dispatcher;
 pub mod environment;
 pub mod error;
 pub mod event;
+pub mod event_store;
 pub mod ids;
+pub mod middleware;
+pub mod outbox;
+pub mod policy;
+pub mod repository;
 pub mod request;
+pub mod request_context;
 pub mod role;
+pub mod unit_of_work;
 pub mod use_case;
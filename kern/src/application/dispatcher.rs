@@ -0,0 +1,270 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::Arc,
+};
+
+use crate::application::{error::dispatch_error::DispatchError, use_case::UseCase};
+
+/// A Command is a request that mutates an Aggregate. It can only be sent through the command
+/// channel of a Dispatcher
+pub trait Command: Send + 'static {
+    /// The output of the UseCase that handles the Command
+    type Response: Send + 'static;
+}
+
+/// A Query is a request that returns data without mutating an Aggregate. It can only be sent
+/// through the query channel of a Dispatcher
+pub trait Query: Send + 'static {
+    /// The output of the UseCase that handles the Query
+    type Response: Send + 'static;
+}
+
+/// The UseCase a request is routed to, stored type-erased so UseCases of different request types
+/// can share one registry
+type Handler<R, O> = Arc<dyn UseCase<Request = R, Response = O>>;
+
+/// The UseCases of one channel, keyed by the type of their request
+#[derive(Default)]
+struct Registry {
+    use_cases: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl Registry {
+    fn register<R, O>(&mut self, use_case: Handler<R, O>)
+    where
+        R: 'static,
+        O: 'static,
+    {
+        self.use_cases.insert(TypeId::of::<R>(), Box::new(use_case));
+    }
+
+    fn get<R, O>(&self) -> Result<Handler<R, O>, DispatchError>
+    where
+        R: 'static,
+        O: 'static,
+    {
+        self.use_cases
+            .get(&TypeId::of::<R>())
+            .and_then(|use_case| use_case.downcast_ref::<Handler<R, O>>())
+            .cloned()
+            .ok_or_else(|| DispatchError::unregistered(std::any::type_name::<R>()))
+    }
+
+    fn contains<R>(&self) -> bool
+    where
+        R: 'static,
+    {
+        self.use_cases.contains_key(&TypeId::of::<R>())
+    }
+}
+
+/// The Dispatcher routes Commands and Queries to the UseCases registered for their type, so callers
+/// only depend on the Dispatcher instead of every UseCase.
+///
+/// Commands and Queries travel through separate channels: `send` only accepts Commands and `query`
+/// only accepts Queries, which enforces Command-Query Responsibility Segregation (CQRS) at compile
+/// time
+///
+/// ```
+/// use kern::application::dispatcher::{Command, Dispatcher};
+/// use kern::application::use_case::UseCase;
+///
+/// struct CreateTenant {
+///     name: String,
+/// }
+///
+/// impl Command for CreateTenant {
+///     type Response = Result<String, String>;
+/// }
+///
+/// struct CreateTenantUseCase;
+///
+/// #[async_trait::async_trait]
+/// impl UseCase for CreateTenantUseCase {
+///     type Request = CreateTenant;
+///     type Response = Result<String, String>;
+///
+///     async fn handle(&self, request: CreateTenant) -> Result<String, String> {
+///         Ok(request.name)
+///     }
+/// }
+///
+/// # tokio_test();
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn tokio_test() {
+/// let mut dispatcher = Dispatcher::new();
+/// dispatcher.register_command(CreateTenantUseCase);
+///
+/// let response = dispatcher
+///     .send(CreateTenant { name: "tenant.a".to_string() })
+///     .await
+///     .unwrap();
+///
+/// assert_eq!(response, Ok("tenant.a".to_string()));
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Dispatcher {
+    commands: Registry,
+    queries: Registry,
+}
+
+impl Dispatcher {
+    /// Creates a new Dispatcher without registered UseCases
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the UseCase as the handler of its Command type, replacing any UseCase that was
+    /// registered for it before
+    /// # Arguments
+    /// * `use_case` - The UseCase that handles the Command
+    pub fn register_command<U>(&mut self, use_case: U) -> &mut Self
+    where
+        U: UseCase + 'static,
+        U::Request: Command<Response = U::Response>,
+    {
+        let use_case: Handler<U::Request, U::Response> = Arc::new(use_case);
+        self.commands.register(use_case);
+        self
+    }
+
+    /// Registers the UseCase as the handler of its Query type, replacing any UseCase that was
+    /// registered for it before
+    /// # Arguments
+    /// * `use_case` - The UseCase that handles the Query
+    pub fn register_query<U>(&mut self, use_case: U) -> &mut Self
+    where
+        U: UseCase + 'static,
+        U::Request: Query<Response = U::Response>,
+    {
+        let use_case: Handler<U::Request, U::Response> = Arc::new(use_case);
+        self.queries.register(use_case);
+        self
+    }
+
+    /// Returns true if a UseCase is registered for the Command type
+    pub fn handles_command<C>(&self) -> bool
+    where
+        C: Command,
+    {
+        self.commands.contains::<C>()
+    }
+
+    /// Returns true if a UseCase is registered for the Query type
+    pub fn handles_query<Q>(&self) -> bool
+    where
+        Q: Query,
+    {
+        self.queries.contains::<Q>()
+    }
+
+    /// Sends the Command to the UseCase registered for its type and returns its Response
+    /// # Arguments
+    /// * `command` - The Command to handle
+    pub async fn send<C>(&self, command: C) -> Result<C::Response, DispatchError>
+    where
+        C: Command,
+    {
+        let use_case = self.commands.get::<C, C::Response>()?;
+        Ok(use_case.handle(command).await)
+    }
+
+    /// Sends the Query to the UseCase registered for its type and returns its Response
+    /// # Arguments
+    /// * `query` - The Query to handle
+    pub async fn query<Q>(&self, query: Q) -> Result<Q::Response, DispatchError>
+    where
+        Q: Query,
+    {
+        let use_case = self.queries.get::<Q, Q::Response>()?;
+        Ok(use_case.handle(query).await)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Rename {
+        name: String,
+    }
+
+    impl Command for Rename {
+        type Response = String;
+    }
+
+    struct FindName;
+
+    impl Query for FindName {
+        type Response = Option<String>;
+    }
+
+    struct RenameUseCase;
+
+    #[async_trait::async_trait]
+    impl UseCase for RenameUseCase {
+        type Request = Rename;
+        type Response = String;
+
+        async fn handle(&self, request: Rename) -> String {
+            request.name
+        }
+    }
+
+    struct FindNameUseCase;
+
+    #[async_trait::async_trait]
+    impl UseCase for FindNameUseCase {
+        type Request = FindName;
+        type Response = Option<String>;
+
+        async fn handle(&self, _: FindName) -> Option<String> {
+            Some("name".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn given_registered_use_cases_when_dispatching_then_requests_are_routed_by_type() {
+        let mut dispatcher = Dispatcher::new();
+        dispatcher
+            .register_command(RenameUseCase)
+            .register_query(FindNameUseCase);
+        let dispatcher = Arc::new(dispatcher);
+
+        let renamed = tokio::spawn({
+            let dispatcher = dispatcher.clone();
+            async move {
+                dispatcher
+                    .send(Rename {
+                        name: "renamed".to_string(),
+                    })
+                    .await
+            }
+        });
+
+        assert_eq!(renamed.await.unwrap(), Ok("renamed".to_string()));
+        assert_eq!(
+            dispatcher.query(FindName).await,
+            Ok(Some("name".to_string()))
+        );
+        assert!(dispatcher.handles_command::<Rename>());
+        assert!(dispatcher.handles_query::<FindName>());
+    }
+
+    #[tokio::test]
+    async fn given_no_registered_use_case_when_dispatching_then_an_error_is_returned() {
+        let dispatcher = Dispatcher::new();
+
+        let result = dispatcher.query(FindName).await;
+
+        assert_eq!(
+            result,
+            Err(DispatchError::unregistered(
+                std::any::type_name::<FindName>()
+            ))
+        );
+        assert!(!dispatcher.handles_command::<Rename>());
+    }
+}
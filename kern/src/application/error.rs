@@ -1 +1,14 @@
+pub mod application_error;
+pub mod commit_error;
+pub mod concurrency_conflict;
+pub mod conflict_error;
+pub mod dispatch_error;
+pub mod event_store_error;
 pub mod forbidden_error;
+pub mod not_found_error;
+pub mod outbox_error;
+pub mod publish_error;
+pub mod repository_error;
+pub mod timeout_error;
+pub mod unauthorized_error;
+pub mod unavailable_error;
@@ -0,0 +1,171 @@
+use crate::{
+    application::error::{
+        commit_error::CommitError, concurrency_conflict::ConcurrencyConflict,
+        conflict_error::ConflictError, forbidden_error::ForbiddenError,
+        not_found_error::NotFoundError, repository_error::RepositoryError,
+        timeout_error::TimeoutError, unauthorized_error::UnauthorizedError,
+        unavailable_error::UnavailableError,
+    },
+    building_blocks::error::{domain_error::DomainError, error_detail::ErrorDetail},
+};
+
+crate::declare_errors! {
+    /// The request did not complete within its timeout
+    pub REQUEST_TIMEOUT = "error.request.timeout" => "The request did not complete in time";
+    /// The storage failed, the cause is not exposed to the caller
+    pub STORAGE_UNAVAILABLE = "error.storage.unavailable" => "The storage is unavailable";
+    /// Some Aggregates of a UnitOfWork were saved before another one failed
+    pub PARTIAL_COMMIT = "error.unit-of-work.partial-commit" => "Only some of the changes were saved";
+}
+
+/// An ApplicationError is any error a UseCase can return to its caller. A UseCase whose Response
+/// is `Result<T, ApplicationError>` can be returned straight from an HTTP handler, which maps every
+/// variant to its status code
+///
+/// ```
+/// use kern::application::error::application_error::ApplicationError;
+/// use kern::application::error::concurrency_conflict::ConcurrencyConflict;
+/// use kern::application::error::not_found_error::NotFoundError;
+/// use kern::building_blocks::error::error_detail::ErrorDetail;
+///
+/// fn find_tenant(exists: bool) -> Result<(), ApplicationError> {
+///     if !exists {
+///         return Err(NotFoundError::new(ErrorDetail::new(
+///             "error.tenant.not-found",
+///             "The tenant does not exist",
+///         ))
+///         .into());
+///     }
+///     Err(ConcurrencyConflict::new("tenant", 1, 2).into())
+/// }
+///
+/// assert!(matches!(find_tenant(false), Err(ApplicationError::NotFound(_))));
+/// assert!(matches!(find_tenant(true), Err(ApplicationError::Conflict(_))));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ApplicationError {
+    /// The request violates a business rule
+    Domain(DomainError),
+    /// A resource the request needs does not exist
+    NotFound(NotFoundError),
+    /// The request conflicts with the current state of a resource
+    Conflict(ConflictError),
+    /// The executor of the request is not allowed to execute it
+    Forbidden(ForbiddenError),
+    /// The identity of the executor of the request cannot be established
+    Unauthorized(UnauthorizedError),
+    /// The request cannot be handled right now
+    Unavailable(UnavailableError),
+}
+
+impl ApplicationError {
+    /// The ErrorDetails describing the ApplicationError
+    pub fn error_details(&self) -> Box<dyn Iterator<Item = &ErrorDetail> + '_> {
+        match self {
+            Self::Domain(error) => error.error_details(),
+            Self::NotFound(error) => Box::new(std::iter::once(error.error_detail())),
+            Self::Conflict(error) => Box::new(std::iter::once(error.error_detail())),
+            Self::Forbidden(error) => Box::new(std::iter::once(error.error_detail())),
+            Self::Unauthorized(error) => Box::new(std::iter::once(error.error_detail())),
+            Self::Unavailable(error) => Box::new(std::iter::once(error.error_detail())),
+        }
+    }
+}
+
+impl From<DomainError> for ApplicationError {
+    fn from(value: DomainError) -> Self {
+        Self::Domain(value)
+    }
+}
+
+impl From<NotFoundError> for ApplicationError {
+    fn from(value: NotFoundError) -> Self {
+        Self::NotFound(value)
+    }
+}
+
+impl From<ConflictError> for ApplicationError {
+    fn from(value: ConflictError) -> Self {
+        Self::Conflict(value)
+    }
+}
+
+impl From<ForbiddenError> for ApplicationError {
+    fn from(value: ForbiddenError) -> Self {
+        Self::Forbidden(value)
+    }
+}
+
+impl From<UnauthorizedError> for ApplicationError {
+    fn from(value: UnauthorizedError) -> Self {
+        Self::Unauthorized(value)
+    }
+}
+
+impl From<UnavailableError> for ApplicationError {
+    fn from(value: UnavailableError) -> Self {
+        Self::Unavailable(value)
+    }
+}
+
+impl From<ConcurrencyConflict> for ApplicationError {
+    fn from(value: ConcurrencyConflict) -> Self {
+        Self::Conflict(value.into())
+    }
+}
+
+impl From<TimeoutError> for ApplicationError {
+    fn from(_: TimeoutError) -> Self {
+        Self::Unavailable(UnavailableError::new(REQUEST_TIMEOUT))
+    }
+}
+
+/// The cause of a storage failure is not exposed to the caller
+impl From<RepositoryError> for ApplicationError {
+    fn from(value: RepositoryError) -> Self {
+        match value {
+            RepositoryError::ConcurrencyConflict(conflict) => conflict.into(),
+            RepositoryError::Domain(error) => Self::Domain(error),
+            RepositoryError::Storage(_) => {
+                Self::Unavailable(UnavailableError::new(STORAGE_UNAVAILABLE))
+            }
+        }
+    }
+}
+
+/// A commit that failed after some Aggregates were saved is a Conflict that must not be handled
+/// again as if nothing was saved, a commit that saved nothing is mapped like its RepositoryError
+impl From<CommitError> for ApplicationError {
+    fn from(value: CommitError) -> Self {
+        match value.is_partial() {
+            true => Self::Conflict(ConflictError::new(PARTIAL_COMMIT)),
+            false => value.into_error().into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ApplicationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Domain(error) => write!(f, "{error}"),
+            Self::NotFound(error) => write!(f, "{error}"),
+            Self::Conflict(error) => write!(f, "{error}"),
+            Self::Forbidden(error) => write!(f, "{error}"),
+            Self::Unauthorized(error) => write!(f, "{error}"),
+            Self::Unavailable(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ApplicationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Domain(error) => Some(error),
+            Self::NotFound(error) => Some(error),
+            Self::Conflict(error) => Some(error),
+            Self::Forbidden(error) => Some(error),
+            Self::Unauthorized(error) => Some(error),
+            Self::Unavailable(error) => Some(error),
+        }
+    }
+}
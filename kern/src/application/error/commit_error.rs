@@ -0,0 +1,65 @@
+use crate::application::error::repository_error::RepositoryError;
+
+/// A CommitError is an error that is returned when a UnitOfWork could not save one of its tracked
+/// Aggregates. Saves to different Repositories do not share a transaction, so the Aggregates that
+/// were tracked before the failed one may already be saved and their Domain Events published
+#[derive(Debug)]
+pub struct CommitError {
+    /// The type names of the Aggregates that were saved, in the order they were tracked
+    committed: Vec<&'static str>,
+    /// The error the Repository of the failed Aggregate returned
+    error: RepositoryError,
+}
+
+impl CommitError {
+    /// Creates a CommitError
+    /// # Arguments
+    /// * `committed` - The type names of the Aggregates that were saved, in the order they were
+    ///   tracked
+    /// * `error` - The error the Repository of the failed Aggregate returned
+    pub fn new(committed: Vec<&'static str>, error: RepositoryError) -> Self {
+        Self { committed, error }
+    }
+
+    /// The type names of the Aggregates that were saved, in the order they were tracked. They are
+    /// the first Aggregates that were tracked, the failed Aggregate is the one tracked after them
+    pub fn committed(&self) -> &[&'static str] {
+        &self.committed
+    }
+
+    /// Returns true if some Aggregates were saved before the failure
+    pub fn is_partial(&self) -> bool {
+        !self.committed.is_empty()
+    }
+
+    /// The error the Repository of the failed Aggregate returned
+    pub fn error(&self) -> &RepositoryError {
+        &self.error
+    }
+
+    /// Consumes the CommitError and returns the error the Repository of the failed Aggregate
+    /// returned
+    pub fn into_error(self) -> RepositoryError {
+        self.error
+    }
+}
+
+impl std::fmt::Display for CommitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.is_partial() {
+            true => write!(
+                f,
+                "Committed [{}] before failing: {}",
+                self.committed.join(", "),
+                self.error
+            ),
+            false => write!(f, "{}", self.error),
+        }
+    }
+}
+
+impl std::error::Error for CommitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
@@ -0,0 +1,63 @@
+use crate::building_blocks::error::error_detail::ErrorDetail;
+
+/// A ConcurrencyConflict is an error that is returned when an Aggregate is written with a version
+/// that no longer matches the version that is currently persisted
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConcurrencyConflict {
+    /// The error prefix of the Aggregate
+    error_prefix: &'static str,
+    /// The version the writer expected to be persisted
+    expected_version: u32,
+    /// The version that is actually persisted
+    actual_version: u32,
+}
+
+impl ConcurrencyConflict {
+    /// Creates a ConcurrencyConflict
+    /// # Arguments
+    /// * `error_prefix` - The error prefix of the Aggregate, see `TypeName::error_prefix`
+    /// * `expected_version` - The version the writer expected to be persisted
+    /// * `actual_version` - The version that is actually persisted
+    pub fn new(error_prefix: &'static str, expected_version: u32, actual_version: u32) -> Self {
+        Self {
+            error_prefix,
+            expected_version,
+            actual_version,
+        }
+    }
+
+    /// The error prefix of the Aggregate
+    pub fn error_prefix(&self) -> &'static str {
+        self.error_prefix
+    }
+
+    /// The version the writer expected to be persisted
+    pub fn expected_version(&self) -> u32 {
+        self.expected_version
+    }
+
+    /// The version that is actually persisted
+    pub fn actual_version(&self) -> u32 {
+        self.actual_version
+    }
+
+    /// The error detail that describes the ConcurrencyConflict
+    pub fn error_detail(&self) -> ErrorDetail {
+        ErrorDetail::new(
+            format!("error.{}.concurrency-conflict", self.error_prefix),
+            format!(
+                "Expected version {} but found version {}",
+                self.expected_version, self.actual_version
+            ),
+        )
+    }
+}
+
+impl std::fmt::Display for ConcurrencyConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let detail = self.error_detail();
+        write!(f, "{}: {}", detail.key(), detail.message())
+    }
+}
+
+impl std::error::Error for ConcurrencyConflict {}
@@ -0,0 +1,59 @@
+use crate::{
+    application::error::concurrency_conflict::ConcurrencyConflict,
+    building_blocks::error::error_detail::ErrorDetail,
+};
+
+/// A ConflictError is an error that is returned when a business process conflicts with the current
+/// state of a resource, e.g. because another writer modified the Aggregate in the meantime
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConflictError {
+    /// The error detail that describes the ConflictError
+    error_detail: ErrorDetail,
+    /// Whether the ConflictError was created from a ConcurrencyConflict
+    concurrency_conflict: bool,
+}
+
+impl ConflictError {
+    /// Creates a ConflictError
+    /// # Arguments
+    /// * `error_detail` - The error detail that describes the ConflictError
+    pub fn new(error_detail: ErrorDetail) -> Self {
+        Self {
+            error_detail,
+            concurrency_conflict: false,
+        }
+    }
+
+    /// The error detail that describes the ConflictError
+    pub fn error_detail(&self) -> &ErrorDetail {
+        &self.error_detail
+    }
+
+    /// Returns true if the ConflictError was created from a ConcurrencyConflict, i.e. another
+    /// writer modified the Aggregate in the meantime and handling the request again may succeed
+    pub fn is_concurrency_conflict(&self) -> bool {
+        self.concurrency_conflict
+    }
+}
+
+impl std::fmt::Display for ConflictError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let error_string = format!(
+            "{}: {}",
+            self.error_detail.key().to_owned(),
+            self.error_detail.message().to_owned()
+        );
+        write!(f, "{}", error_string)
+    }
+}
+
+impl std::error::Error for ConflictError {}
+
+impl From<ConcurrencyConflict> for ConflictError {
+    fn from(value: ConcurrencyConflict) -> Self {
+        Self {
+            error_detail: value.error_detail(),
+            concurrency_conflict: true,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+/// A DispatchError is an error that is returned when a Dispatcher cannot route a request to a
+/// UseCase
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DispatchError {
+    /// No UseCase is registered for the type of the request
+    Unregistered {
+        /// The type name of the request
+        request_type: &'static str,
+    },
+}
+
+impl DispatchError {
+    /// Creates a DispatchError::Unregistered
+    /// # Arguments
+    /// * `request_type` - The type name of the request
+    pub fn unregistered(request_type: &'static str) -> Self {
+        Self::Unregistered { request_type }
+    }
+}
+
+impl std::fmt::Display for DispatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unregistered { request_type } => {
+                write!(f, "No use case is registered for {request_type}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DispatchError {}
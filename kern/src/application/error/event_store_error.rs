@@ -0,0 +1,87 @@
+use crate::{
+    application::error::concurrency_conflict::ConcurrencyConflict, building_blocks::ids::EventId,
+};
+
+/// An EventStoreError is any error that is returned by an EventStore
+#[derive(Debug)]
+pub enum EventStoreError {
+    /// The stream was appended to by another writer since it was read
+    ConcurrencyConflict(ConcurrencyConflict),
+    /// An appended event does not continue the version sequence of its stream
+    NonContiguousVersion {
+        /// The version the event was expected to have
+        expected_version: u32,
+        /// The version the event actually has
+        actual_version: u32,
+    },
+    /// An appended event would exceed the highest version of a stream
+    VersionOverflow {
+        /// The identifier of the event
+        event_id: EventId,
+    },
+    /// An appended event belongs to another Aggregate than the stream it is appended to
+    AggregateMismatch {
+        /// The identifier of the mismatching event
+        event_id: EventId,
+    },
+    /// An event with the same identifier was already appended
+    DuplicateEvent {
+        /// The identifier of the duplicated event
+        event_id: EventId,
+    },
+    /// The underlying storage failed
+    Storage(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl EventStoreError {
+    /// Creates an EventStoreError::Storage
+    /// # Arguments
+    /// * `error` - The error returned by the underlying storage
+    pub fn storage<E>(error: E) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        Self::Storage(error.into())
+    }
+}
+
+impl From<ConcurrencyConflict> for EventStoreError {
+    fn from(value: ConcurrencyConflict) -> Self {
+        Self::ConcurrencyConflict(value)
+    }
+}
+
+impl std::fmt::Display for EventStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ConcurrencyConflict(error) => write!(f, "{error}"),
+            Self::NonContiguousVersion {
+                expected_version,
+                actual_version,
+            } => write!(
+                f,
+                "Expected event version {expected_version} but found version {actual_version}"
+            ),
+            Self::VersionOverflow { event_id } => {
+                write!(f, "Event {} exceeds the highest version", event_id.value())
+            }
+            Self::AggregateMismatch { event_id } => {
+                write!(f, "Event {} belongs to another aggregate", event_id.value())
+            }
+            Self::DuplicateEvent { event_id } => {
+                write!(f, "Event {} was already appended", event_id.value())
+            }
+            Self::Storage(error) => write!(f, "Storage error: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for EventStoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ConcurrencyConflict(error) => Some(error),
+            Self::Storage(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
@@ -16,6 +16,16 @@ impl ForbiddenError {
         Self { error_detail }
     }
 
+    /// Creates a ForbiddenError with the key `error.<use-case>.forbidden`
+    /// # Arguments
+    /// * `use_case` - The name of the UseCase the executor is not allowed to execute
+    pub fn for_use_case(use_case: &str) -> Self {
+        Self::new(ErrorDetail::new(
+            format!("error.{use_case}.forbidden"),
+            format!("Not allowed to execute {use_case}"),
+        ))
+    }
+
     /// The error detail that describes the ForbiddenError
     pub fn error_detail(&self) -> &ErrorDetail {
         &self.error_detail
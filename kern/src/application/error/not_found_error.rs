@@ -0,0 +1,36 @@
+use crate::building_blocks::error::error_detail::ErrorDetail;
+
+/// A NotFoundError is an error that is returned when the resource a business process needs does
+/// not exist
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NotFoundError {
+    /// The error detail that describes the NotFoundError
+    error_detail: ErrorDetail,
+}
+
+impl NotFoundError {
+    /// Creates a NotFoundError
+    /// # Arguments
+    /// * `error_detail` - The error detail that describes the NotFoundError
+    pub fn new(error_detail: ErrorDetail) -> Self {
+        Self { error_detail }
+    }
+
+    /// The error detail that describes the NotFoundError
+    pub fn error_detail(&self) -> &ErrorDetail {
+        &self.error_detail
+    }
+}
+
+impl std::fmt::Display for NotFoundError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let error_string = format!(
+            "{}: {}",
+            self.error_detail.key().to_owned(),
+            self.error_detail.message().to_owned()
+        );
+        write!(f, "{}", error_string)
+    }
+}
+
+impl std::error::Error for NotFoundError {}
@@ -0,0 +1,41 @@
+/// An OutboxError is any error that is returned by an Outbox
+#[derive(Debug)]
+pub enum OutboxError {
+    /// No message with the identifier is in the Outbox
+    UnknownMessage {
+        /// The identifier of the message
+        id: u64,
+    },
+    /// The underlying storage failed
+    Storage(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl OutboxError {
+    /// Creates an OutboxError::Storage
+    /// # Arguments
+    /// * `error` - The error returned by the underlying storage
+    pub fn storage<E>(error: E) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        Self::Storage(error.into())
+    }
+}
+
+impl std::fmt::Display for OutboxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownMessage { id } => write!(f, "Unknown outbox message {id}"),
+            Self::Storage(error) => write!(f, "Storage error: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for OutboxError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Storage(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+/// A PublishError is an error that is returned when a domain event could not be handed over to
+/// the handlers of its topic
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublishError {
+    /// The topic the domain event was published to
+    topic: &'static str,
+    /// The reason the domain event could not be published
+    reason: String,
+}
+
+impl PublishError {
+    /// Creates a PublishError
+    /// # Arguments
+    /// * `topic` - The topic the domain event was published to
+    /// * `reason` - The reason the domain event could not be published
+    pub fn new<R>(topic: &'static str, reason: R) -> Self
+    where
+        R: Into<String>,
+    {
+        Self {
+            topic,
+            reason: reason.into(),
+        }
+    }
+
+    /// The topic the domain event was published to
+    pub fn topic(&self) -> &'static str {
+        self.topic
+    }
+
+    /// The reason the domain event could not be published
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl std::fmt::Display for PublishError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Failed to publish to '{}': {}", self.topic, self.reason)
+    }
+}
+
+impl std::error::Error for PublishError {}
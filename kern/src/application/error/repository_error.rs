@@ -0,0 +1,60 @@
+use crate::{
+    application::error::concurrency_conflict::ConcurrencyConflict,
+    building_blocks::error::domain_error::DomainError,
+};
+
+/// A RepositoryError is any error that is returned by a Repository
+#[derive(Debug)]
+pub enum RepositoryError {
+    /// The Aggregate was modified by another writer since it was loaded
+    ConcurrencyConflict(ConcurrencyConflict),
+    /// The Aggregate violates a domain rule of the Repository, e.g. its version cannot be
+    /// incremented any further
+    Domain(DomainError),
+    /// The underlying storage failed
+    Storage(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl RepositoryError {
+    /// Creates a RepositoryError::Storage
+    /// # Arguments
+    /// * `error` - The error returned by the underlying storage
+    pub fn storage<E>(error: E) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        Self::Storage(error.into())
+    }
+}
+
+impl From<ConcurrencyConflict> for RepositoryError {
+    fn from(value: ConcurrencyConflict) -> Self {
+        Self::ConcurrencyConflict(value)
+    }
+}
+
+impl From<DomainError> for RepositoryError {
+    fn from(value: DomainError) -> Self {
+        Self::Domain(value)
+    }
+}
+
+impl std::fmt::Display for RepositoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ConcurrencyConflict(error) => write!(f, "{error}"),
+            Self::Domain(error) => write!(f, "{error}"),
+            Self::Storage(error) => write!(f, "Storage error: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for RepositoryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ConcurrencyConflict(error) => Some(error),
+            Self::Domain(error) => Some(error),
+            Self::Storage(error) => Some(error.as_ref()),
+        }
+    }
+}
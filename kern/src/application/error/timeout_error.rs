@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+/// A TimeoutError is an error that is returned when a UseCase did not handle a request within its
+/// time limit
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimeoutError {
+    /// The type name of the UseCase
+    use_case: &'static str,
+    /// The time limit of the UseCase
+    timeout: Duration,
+}
+
+impl TimeoutError {
+    /// Creates a TimeoutError
+    /// # Arguments
+    /// * `use_case` - The type name of the UseCase
+    /// * `timeout` - The time limit of the UseCase
+    pub fn new(use_case: &'static str, timeout: Duration) -> Self {
+        Self { use_case, timeout }
+    }
+
+    /// The type name of the UseCase
+    pub fn use_case(&self) -> &'static str {
+        self.use_case
+    }
+
+    /// The time limit of the UseCase
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl std::fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} did not complete within {:?}",
+            self.use_case, self.timeout
+        )
+    }
+}
+
+impl std::error::Error for TimeoutError {}
@@ -0,0 +1,36 @@
+use crate::building_blocks::error::error_detail::ErrorDetail;
+
+/// An UnauthorizedError is an error that is returned when the identity of the executor of a
+/// business process cannot be established, e.g. because the credentials are missing or invalid
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnauthorizedError {
+    /// The error detail that describes the UnauthorizedError
+    error_detail: ErrorDetail,
+}
+
+impl UnauthorizedError {
+    /// Creates an UnauthorizedError
+    /// # Arguments
+    /// * `error_detail` - The error detail that describes the UnauthorizedError
+    pub fn new(error_detail: ErrorDetail) -> Self {
+        Self { error_detail }
+    }
+
+    /// The error detail that describes the UnauthorizedError
+    pub fn error_detail(&self) -> &ErrorDetail {
+        &self.error_detail
+    }
+}
+
+impl std::fmt::Display for UnauthorizedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let error_string = format!(
+            "{}: {}",
+            self.error_detail.key().to_owned(),
+            self.error_detail.message().to_owned()
+        );
+        write!(f, "{}", error_string)
+    }
+}
+
+impl std::error::Error for UnauthorizedError {}
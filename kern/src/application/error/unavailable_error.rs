@@ -0,0 +1,36 @@
+use crate::building_blocks::error::error_detail::ErrorDetail;
+
+/// An UnavailableError is an error that is returned when a business process cannot be executed
+/// right now because a dependency, such as the storage, is unavailable
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnavailableError {
+    /// The error detail that describes the UnavailableError
+    error_detail: ErrorDetail,
+}
+
+impl UnavailableError {
+    /// Creates an UnavailableError
+    /// # Arguments
+    /// * `error_detail` - The error detail that describes the UnavailableError
+    pub fn new(error_detail: ErrorDetail) -> Self {
+        Self { error_detail }
+    }
+
+    /// The error detail that describes the UnavailableError
+    pub fn error_detail(&self) -> &ErrorDetail {
+        &self.error_detail
+    }
+}
+
+impl std::fmt::Display for UnavailableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let error_string = format!(
+            "{}: {}",
+            self.error_detail.key().to_owned(),
+            self.error_detail.message().to_owned()
+        );
+        write!(f, "{}", error_string)
+    }
+}
+
+impl std::error::Error for UnavailableError {}
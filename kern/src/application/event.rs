@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use crate::building_blocks::domain_event::DynDomainEvent;
+use crate::{
+    application::error::publish_error::PublishError, building_blocks::domain_event::DynDomainEvent,
+};
 
 /// The EventPublisher sends the Domain Event to the local message queu
 pub trait EventPublisher: Send + Sync {
@@ -9,6 +11,31 @@ pub trait EventPublisher: Send + Sync {
     /// * `topic` - The topic the domain event will be published to
     /// * `event` - The domain event to be published
     fn publish(&self, topic: &'static str, event: Arc<dyn DynDomainEvent>);
+
+    /// Publishes the domain event to the topic and reports whether it was handed over. Publishers
+    /// that can detect failed deliveries should override it, the default always succeeds
+    /// # Arguments
+    /// * `topic` - The topic the domain event will be published to
+    /// * `event` - The domain event to be published
+    fn try_publish(
+        &self,
+        topic: &'static str,
+        event: Arc<dyn DynDomainEvent>,
+    ) -> Result<(), PublishError> {
+        self.publish(topic, event);
+        Ok(())
+    }
+
+    /// Publishes the domain events to the topic in order, e.g. the events taken from an Aggregate
+    /// that RecordsEvents
+    /// # Arguments
+    /// * `topic` - The topic the domain events will be published to
+    /// * `events` - The domain events to be published
+    fn publish_all(&self, topic: &'static str, events: Vec<Arc<dyn DynDomainEvent>>) {
+        for event in events {
+            self.publish(topic, event);
+        }
+    }
 }
 
 /// The EventHandler acts on the Domain Event it receives from the EventBus
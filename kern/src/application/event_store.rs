@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use crate::{
+    application::error::event_store_error::EventStoreError,
+    building_blocks::{domain_event::DomainEvent, event_sourced::EventSourced},
+};
+
+/// The identifier of an EventSourced Aggregate's event stream
+pub type StreamId<A> = <<A as EventSourced>::Event as DomainEvent>::Id;
+
+/// A RecordedEvent is a Domain Event together with its position in the global order of the
+/// EventStore
+#[derive(Debug)]
+pub struct RecordedEvent<E> {
+    /// The position of the Domain Event across all streams of the EventStore, starting at 0
+    position: u64,
+    /// The Domain Event
+    event: Arc<E>,
+}
+
+impl<E> RecordedEvent<E> {
+    /// Creates a RecordedEvent
+    /// # Arguments
+    /// * `position` - The position of the Domain Event across all streams of the EventStore
+    /// * `event` - The Domain Event
+    pub fn new(position: u64, event: Arc<E>) -> Self {
+        Self { position, event }
+    }
+
+    /// The position of the Domain Event across all streams of the EventStore, starting at 0
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// The Domain Event
+    pub fn event(&self) -> &Arc<E> {
+        &self.event
+    }
+
+    /// Consumes the RecordedEvent and returns the Domain Event
+    pub fn into_event(self) -> Arc<E> {
+        self.event
+    }
+}
+
+impl<E> Clone for RecordedEvent<E> {
+    fn clone(&self) -> Self {
+        Self {
+            position: self.position,
+            event: self.event.clone(),
+        }
+    }
+}
+
+/// An EventStore persists the Domain Events of EventSourced Aggregates as append-only streams, one
+/// stream per AggregateId. Appends use optimistic concurrency on the version of the stream, which
+/// is the `aggregate_version` of its last Domain Event, or 0 for an empty stream.
+///
+/// The Domain Events appended to a stream must continue its versions without gaps or duplicates:
+/// the first appended event has the version `expected_version + 1`, the next one
+/// `expected_version + 2` and so on
+#[async_trait::async_trait]
+pub trait EventStore<A>: Send + Sync
+where
+    A: EventSourced,
+    A::Event: Send + Sync + 'static,
+    StreamId<A>: Send + Sync,
+{
+    /// Appends the Domain Events to the stream of the Aggregate
+    /// # Arguments
+    /// * `aggregate_id` - The identifier of the Aggregate whose stream is appended to
+    /// * `expected_version` - The version the stream is expected to have before the append
+    /// * `events` - The Domain Events to append
+    async fn append(
+        &self,
+        aggregate_id: &StreamId<A>,
+        expected_version: u32,
+        events: Vec<A::Event>,
+    ) -> Result<(), EventStoreError>;
+
+    /// Reads the Domain Events of the Aggregate's stream whose version is greater than or equal to
+    /// `from_version`, ordered by version
+    /// # Arguments
+    /// * `aggregate_id` - The identifier of the Aggregate whose stream is read
+    /// * `from_version` - The version of the first Domain Event to read
+    async fn read_stream(
+        &self,
+        aggregate_id: &StreamId<A>,
+        from_version: u32,
+    ) -> Result<Vec<Arc<A::Event>>, EventStoreError>;
+
+    /// Reads the Domain Events of all streams whose position is greater than or equal to
+    /// `from_position`, in the order they were appended
+    /// # Arguments
+    /// * `from_position` - The position of the first Domain Event to read
+    async fn read_all(
+        &self,
+        from_position: u64,
+    ) -> Result<Vec<RecordedEvent<A::Event>>, EventStoreError>;
+}
+
+/// Validates that the Domain Events belong to the stream and continue its versions without gaps
+/// or duplicates. Shared by the EventStore implementations
+/// # Arguments
+/// * `aggregate_id` - The identifier of the Aggregate whose stream is appended to
+/// * `expected_version` - The version of the stream before the append
+/// * `events` - The Domain Events to append
+pub fn validate_append<E>(
+    aggregate_id: &E::Id,
+    expected_version: u32,
+    events: &[E],
+) -> Result<(), EventStoreError>
+where
+    E: DomainEvent,
+{
+    for (index, event) in events.iter().enumerate() {
+        if event.aggregate_id() != aggregate_id {
+            return Err(EventStoreError::AggregateMismatch {
+                event_id: *event.id(),
+            });
+        }
+        let version = u32::try_from(index)
+            .ok()
+            .and_then(|index| expected_version.checked_add(index))
+            .and_then(|version| version.checked_add(1))
+            .ok_or(EventStoreError::VersionOverflow {
+                event_id: *event.id(),
+            })?;
+        if event.aggregate_version() != version {
+            return Err(EventStoreError::NonContiguousVersion {
+                expected_version: version,
+                actual_version: event.aggregate_version(),
+            });
+        }
+    }
+    Ok(())
+}
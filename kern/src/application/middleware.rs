@@ -0,0 +1,340 @@
+use chrono::{DateTime, Utc};
+
+use crate::application::{environment::Environment, request::Request};
+
+pub mod authorization;
+pub mod logging;
+pub mod retry;
+#[cfg(feature = "timeout")]
+pub mod timeout;
+pub mod timing;
+pub mod validation;
+
+/// A UseCaseLayer wraps a UseCase in another UseCase that adds a reusable behavior, such as
+/// logging or authorization, without editing the body of the wrapped UseCase
+pub trait UseCaseLayer<U> {
+    /// The UseCase that wraps the inner UseCase
+    type UseCase;
+
+    /// Wraps the UseCase
+    /// # Arguments
+    /// * `inner` - The UseCase to wrap
+    fn layer(&self, inner: U) -> Self::UseCase;
+}
+
+/// The Identity is a UseCaseLayer that returns the UseCase unchanged
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Identity;
+
+impl<U> UseCaseLayer<U> for Identity {
+    type UseCase = U;
+
+    fn layer(&self, inner: U) -> U {
+        inner
+    }
+}
+
+/// A Stack is a UseCaseLayer that applies the inner layer first and wraps the result in the outer
+/// layer
+#[derive(Clone, Copy, Debug)]
+pub struct Stack<Inner, Outer> {
+    inner: Inner,
+    outer: Outer,
+}
+
+impl<U, Inner, Outer> UseCaseLayer<U> for Stack<Inner, Outer>
+where
+    Inner: UseCaseLayer<U>,
+    Outer: UseCaseLayer<Inner::UseCase>,
+{
+    type UseCase = Outer::UseCase;
+
+    fn layer(&self, inner: U) -> Self::UseCase {
+        self.outer.layer(self.inner.layer(inner))
+    }
+}
+
+/// The UseCaseBuilder stacks UseCaseLayers and wraps a UseCase in them. The layer that is added
+/// first is the outermost one, so it sees the request first and the response last
+///
+/// ```
+/// use kern::application::middleware::{
+///     RequestMetadata, UseCaseBuilder, retry::RetryLayer, timing::TimingLayer,
+/// };
+/// use kern::application::error::repository_error::RepositoryError;
+/// use kern::application::use_case::UseCase;
+/// use kern::application::ids::RequestId;
+/// use kern::application::environment::Environment;
+/// use chrono::{DateTime, Utc};
+///
+/// #[derive(kern::Request, Clone, Debug)]
+/// pub struct CloseAccount {
+///     request_id: RequestId,
+///     authorized_party: (),
+///     environment: Environment,
+///     issued_at: DateTime<Utc>,
+/// }
+///
+/// struct CloseAccountUseCase;
+///
+/// #[async_trait::async_trait]
+/// impl UseCase for CloseAccountUseCase {
+///     type Request = CloseAccount;
+///     type Response = Result<(), RepositoryError>;
+///
+///     async fn handle(&self, _: CloseAccount) -> Result<(), RepositoryError> {
+///         Ok(())
+///     }
+/// }
+///
+/// let use_case = UseCaseBuilder::new()
+///     .layer(TimingLayer::new(|metadata: &RequestMetadata<RequestId>, use_case, elapsed| {
+///         println!("{use_case} handled {:?} in {elapsed:?}", metadata.request_id());
+///     }))
+///     .layer(RetryLayer::new(3))
+///     .build(CloseAccountUseCase);
+///
+/// # tokio_test(use_case);
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn tokio_test(use_case: impl UseCase<Request = CloseAccount, Response = Result<(), RepositoryError>>) {
+/// let response = use_case
+///     .handle(CloseAccount {
+///         request_id: RequestId::new_random_v4(),
+///         authorized_party: (),
+///         environment: Environment::Development,
+///         issued_at: Utc::now(),
+///     })
+///     .await;
+///
+/// assert!(response.is_ok());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct UseCaseBuilder<L> {
+    layer: L,
+}
+
+impl UseCaseBuilder<Identity> {
+    /// Creates a new UseCaseBuilder without layers
+    pub fn new() -> Self {
+        Self { layer: Identity }
+    }
+}
+
+impl Default for UseCaseBuilder<Identity> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L> UseCaseBuilder<L> {
+    /// Adds a layer that wraps the layers that were added after it
+    /// # Arguments
+    /// * `layer` - The layer to add
+    pub fn layer<T>(self, layer: T) -> UseCaseBuilder<Stack<T, L>> {
+        UseCaseBuilder {
+            layer: Stack {
+                inner: layer,
+                outer: self.layer,
+            },
+        }
+    }
+
+    /// Wraps the UseCase in the stacked layers
+    /// # Arguments
+    /// * `use_case` - The UseCase to wrap
+    pub fn build<U>(&self, use_case: U) -> L::UseCase
+    where
+        L: UseCaseLayer<U>,
+    {
+        self.layer.layer(use_case)
+    }
+}
+
+/// The RequestMetadata is a copy of the metadata of a Request, so a layer can still use it after
+/// the Request was handed to the wrapped UseCase
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestMetadata<I> {
+    /// The unique identifier of the Request
+    request_id: I,
+    /// The environment of the Request
+    environment: Environment,
+    /// The timestamp of when the Request was issued
+    issued_at: DateTime<Utc>,
+}
+
+impl<I> RequestMetadata<I>
+where
+    I: Clone,
+{
+    /// Creates the RequestMetadata of a Request
+    /// # Arguments
+    /// * `request` - The Request
+    pub fn of<R>(request: &R) -> Self
+    where
+        R: Request<RequestId = I>,
+    {
+        Self {
+            request_id: request.request_id().clone(),
+            environment: *request.environment(),
+            issued_at: *request.issued_at(),
+        }
+    }
+
+    /// The unique identifier of the Request
+    pub fn request_id(&self) -> &I {
+        &self.request_id
+    }
+
+    /// The environment of the Request
+    pub fn environment(&self) -> &Environment {
+        &self.environment
+    }
+
+    /// The timestamp of when the Request was issued
+    pub fn issued_at(&self) -> &DateTime<Utc> {
+        &self.issued_at
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use super::{
+        authorization::AuthorizationLayer, logging::LoggingLayer, timing::TimingLayer,
+        validation::ValidationLayer, *,
+    };
+    use crate::{
+        application::{
+            error::forbidden_error::ForbiddenError, ids::RequestId, policy::UseCasePolicy,
+            use_case::UseCase,
+        },
+        building_blocks::error::{domain_error::DomainError, error_detail::ErrorDetail},
+    };
+
+    // The derive macros refer to the crate by its name
+    use crate as kern;
+
+    #[derive(crate::Request, Clone, Debug)]
+    struct Withdraw {
+        request_id: RequestId,
+        authorized_party: (),
+        environment: Environment,
+        issued_at: DateTime<Utc>,
+        amount: i64,
+    }
+
+    impl Withdraw {
+        fn new(amount: i64) -> Self {
+            Self {
+                request_id: RequestId::new_random_v4(),
+                authorized_party: (),
+                environment: Environment::Staging,
+                issued_at: Utc::now(),
+                amount,
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum WithdrawError {
+        Invalid(DomainError),
+        Forbidden(ForbiddenError),
+    }
+
+    impl From<DomainError> for WithdrawError {
+        fn from(value: DomainError) -> Self {
+            Self::Invalid(value)
+        }
+    }
+
+    impl From<ForbiddenError> for WithdrawError {
+        fn from(value: ForbiddenError) -> Self {
+            Self::Forbidden(value)
+        }
+    }
+
+    struct WithdrawUseCase {
+        calls: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl UseCase for WithdrawUseCase {
+        type Request = Withdraw;
+        type Response = Result<i64, WithdrawError>;
+
+        async fn handle(&self, request: Withdraw) -> Result<i64, WithdrawError> {
+            self.calls.lock().unwrap().push("handle");
+            Ok(request.amount)
+        }
+    }
+
+    #[tokio::test]
+    async fn given_stacked_layers_when_handling_then_the_first_layer_is_the_outermost() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let use_case = UseCaseBuilder::new()
+            .layer(LoggingLayer::new({
+                let calls = calls.clone();
+                move |metadata: &RequestMetadata<RequestId>, _| {
+                    assert_eq!(*metadata.environment(), Environment::Staging);
+                    calls.lock().unwrap().push("logging");
+                }
+            }))
+            .layer(TimingLayer::new({
+                let calls = calls.clone();
+                move |_: &RequestMetadata<RequestId>, _, _| calls.lock().unwrap().push("timing")
+            }))
+            .layer(ValidationLayer::new({
+                let calls = calls.clone();
+                move |_: &Withdraw| {
+                    calls.lock().unwrap().push("validation");
+                    Ok(())
+                }
+            }))
+            .build(WithdrawUseCase {
+                calls: calls.clone(),
+            });
+
+        assert_eq!(use_case.handle(Withdraw::new(10)).await, Ok(10));
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["logging", "validation", "handle", "timing"]
+        );
+    }
+
+    #[tokio::test]
+    async fn given_a_rejecting_layer_when_handling_then_the_use_case_is_not_called() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let use_case = UseCaseBuilder::new()
+            .layer(AuthorizationLayer::new(UseCasePolicy::new(
+                "withdraw",
+                |request: &Withdraw| request.amount <= 100,
+            )))
+            .layer(ValidationLayer::new(|request: &Withdraw| {
+                if request.amount <= 0 {
+                    Err(DomainError::single(ErrorDetail::new(
+                        "error.withdraw.invalid-amount",
+                        "The amount must be positive",
+                    )))
+                } else {
+                    Ok(())
+                }
+            }))
+            .build(WithdrawUseCase {
+                calls: calls.clone(),
+            });
+
+        assert_eq!(
+            use_case.handle(Withdraw::new(1000)).await,
+            Err(WithdrawError::Forbidden(ForbiddenError::for_use_case(
+                "withdraw"
+            )))
+        );
+        assert!(matches!(
+            use_case.handle(Withdraw::new(0)).await,
+            Err(WithdrawError::Invalid(_))
+        ));
+        assert!(calls.lock().unwrap().is_empty());
+    }
+}
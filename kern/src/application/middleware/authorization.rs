@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use crate::application::{
+    error::forbidden_error::ForbiddenError,
+    middleware::UseCaseLayer,
+    policy::{Policy, UseCasePolicy},
+    use_case::UseCase,
+};
+
+/// An Authorizer checks that the executor of a Request is allowed to execute a UseCase.
+///
+/// Any closure that takes the Request and returns a `Result<(), ForbiddenError>` is an Authorizer,
+/// and so is a UseCasePolicy
+pub trait Authorizer<R>: Send + Sync {
+    /// Returns a ForbiddenError if the executor of the Request is not allowed to execute the
+    /// UseCase
+    /// # Arguments
+    /// * `request` - The Request to authorize
+    fn authorize(&self, request: &R) -> Result<(), ForbiddenError>;
+}
+
+impl<R, F> Authorizer<R> for F
+where
+    F: Fn(&R) -> Result<(), ForbiddenError> + Send + Sync,
+{
+    fn authorize(&self, request: &R) -> Result<(), ForbiddenError> {
+        self(request)
+    }
+}
+
+impl<R, P> Authorizer<R> for UseCasePolicy<P>
+where
+    P: Policy<R>,
+{
+    fn authorize(&self, request: &R) -> Result<(), ForbiddenError> {
+        UseCasePolicy::authorize(self, request)
+    }
+}
+
+/// The AuthorizationLayer checks that the executor of every Request is allowed to execute the
+/// wrapped UseCase. A Request that is not allowed is answered with the ForbiddenError of the
+/// Authorizer and never reaches the wrapped UseCase
+#[derive(Debug)]
+pub struct AuthorizationLayer<A> {
+    authorizer: Arc<A>,
+}
+
+impl<A> Clone for AuthorizationLayer<A> {
+    fn clone(&self) -> Self {
+        Self {
+            authorizer: self.authorizer.clone(),
+        }
+    }
+}
+
+impl<A> AuthorizationLayer<A> {
+    /// Creates a new AuthorizationLayer
+    /// # Arguments
+    /// * `authorizer` - The Authorizer that checks if the Request is allowed, e.g. a UseCasePolicy
+    pub fn new(authorizer: A) -> Self {
+        Self {
+            authorizer: Arc::new(authorizer),
+        }
+    }
+}
+
+impl<U, A> UseCaseLayer<U> for AuthorizationLayer<A> {
+    type UseCase = AuthorizationUseCase<U, A>;
+
+    fn layer(&self, inner: U) -> Self::UseCase {
+        AuthorizationUseCase {
+            inner,
+            authorizer: self.authorizer.clone(),
+        }
+    }
+}
+
+/// The UseCase created by the AuthorizationLayer
+#[derive(Debug)]
+pub struct AuthorizationUseCase<U, A> {
+    inner: U,
+    authorizer: Arc<A>,
+}
+
+#[async_trait::async_trait]
+impl<U, A, T, E> UseCase for AuthorizationUseCase<U, A>
+where
+    U: UseCase<Response = Result<T, E>>,
+    U::Request: Send + 'static,
+    E: From<ForbiddenError>,
+    A: Authorizer<U::Request>,
+{
+    type Request = U::Request;
+    type Response = U::Response;
+
+    async fn handle(&self, request: U::Request) -> U::Response {
+        self.authorizer.authorize(&request)?;
+        self.inner.handle(request).await
+    }
+}
@@ -0,0 +1,59 @@
+use crate::application::{
+    middleware::{RequestMetadata, UseCaseLayer},
+    request::Request,
+    use_case::UseCase,
+};
+
+/// The LoggingLayer calls a logger with the metadata of every Request before it is handled by the
+/// wrapped UseCase
+#[derive(Clone, Debug)]
+pub struct LoggingLayer<F> {
+    logger: F,
+}
+
+impl<F> LoggingLayer<F> {
+    /// Creates a new LoggingLayer
+    /// # Arguments
+    /// * `logger` - The function that is called with the metadata of the Request and the type name
+    ///   of the wrapped UseCase
+    pub fn new(logger: F) -> Self {
+        Self { logger }
+    }
+}
+
+impl<U, F> UseCaseLayer<U> for LoggingLayer<F>
+where
+    F: Clone,
+{
+    type UseCase = LoggingUseCase<U, F>;
+
+    fn layer(&self, inner: U) -> Self::UseCase {
+        LoggingUseCase {
+            inner,
+            logger: self.logger.clone(),
+        }
+    }
+}
+
+/// The UseCase created by the LoggingLayer
+#[derive(Clone, Debug)]
+pub struct LoggingUseCase<U, F> {
+    inner: U,
+    logger: F,
+}
+
+#[async_trait::async_trait]
+impl<U, F> UseCase for LoggingUseCase<U, F>
+where
+    U: UseCase,
+    U::Request: Request + Send + 'static,
+    F: Fn(&RequestMetadata<<U::Request as Request>::RequestId>, &'static str) + Send + Sync,
+{
+    type Request = U::Request;
+    type Response = U::Response;
+
+    async fn handle(&self, request: U::Request) -> U::Response {
+        (self.logger)(&RequestMetadata::of(&request), std::any::type_name::<U>());
+        self.inner.handle(request).await
+    }
+}
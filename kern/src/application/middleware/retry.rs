@@ -0,0 +1,197 @@
+use crate::application::{
+    error::{
+        application_error::ApplicationError, commit_error::CommitError,
+        concurrency_conflict::ConcurrencyConflict, event_store_error::EventStoreError,
+        repository_error::RepositoryError,
+    },
+    middleware::UseCaseLayer,
+    use_case::UseCase,
+};
+
+/// A Retryable error tells whether the Request that failed with it can be handled again, e.g.
+/// because another writer modified the Aggregate in the meantime
+pub trait Retryable {
+    /// Returns true if handling the Request again may succeed
+    fn is_retryable(&self) -> bool;
+}
+
+impl Retryable for ConcurrencyConflict {
+    fn is_retryable(&self) -> bool {
+        true
+    }
+}
+
+impl Retryable for RepositoryError {
+    fn is_retryable(&self) -> bool {
+        matches!(self, Self::ConcurrencyConflict(_))
+    }
+}
+
+impl Retryable for EventStoreError {
+    fn is_retryable(&self) -> bool {
+        matches!(self, Self::ConcurrencyConflict(_))
+    }
+}
+
+/// A failed commit is only retryable if nothing was saved before the failure, otherwise handling
+/// the Request again would apply the saved changes twice
+impl Retryable for CommitError {
+    fn is_retryable(&self) -> bool {
+        !self.is_partial() && self.error().is_retryable()
+    }
+}
+
+/// Only a Conflict that was created from a ConcurrencyConflict is retryable
+impl Retryable for ApplicationError {
+    fn is_retryable(&self) -> bool {
+        matches!(self, Self::Conflict(conflict) if conflict.is_concurrency_conflict())
+    }
+}
+
+/// The RetryLayer handles a Request again when the wrapped UseCase failed with a Retryable error,
+/// such as a ConcurrencyConflict, until it succeeds or the maximum number of attempts is reached.
+/// The wrapped UseCase must reload the Aggregate on every attempt
+#[derive(Clone, Copy, Debug)]
+pub struct RetryLayer {
+    max_attempts: usize,
+}
+
+impl RetryLayer {
+    /// Creates a new RetryLayer
+    /// # Arguments
+    /// * `max_attempts` - The maximum number of times a Request is handled, including the first
+    ///   attempt
+    pub fn new(max_attempts: usize) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+        }
+    }
+}
+
+impl<U> UseCaseLayer<U> for RetryLayer {
+    type UseCase = RetryUseCase<U>;
+
+    fn layer(&self, inner: U) -> Self::UseCase {
+        RetryUseCase {
+            inner,
+            max_attempts: self.max_attempts,
+        }
+    }
+}
+
+/// The UseCase created by the RetryLayer
+#[derive(Clone, Debug)]
+pub struct RetryUseCase<U> {
+    inner: U,
+    max_attempts: usize,
+}
+
+#[async_trait::async_trait]
+impl<U, T, E> UseCase for RetryUseCase<U>
+where
+    U: UseCase<Response = Result<T, E>>,
+    U::Request: Clone + Send + Sync + 'static,
+    E: Retryable,
+{
+    type Request = U::Request;
+    type Response = U::Response;
+
+    async fn handle(&self, request: U::Request) -> U::Response {
+        let mut attempt = 1;
+        loop {
+            match self.inner.handle(request.clone()).await {
+                Err(err) if err.is_retryable() && attempt < self.max_attempts => attempt += 1,
+                response => return response,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{
+        application::error::conflict_error::ConflictError,
+        building_blocks::error::error_detail::ErrorDetail,
+    };
+
+    struct ConflictingUseCase<E = RepositoryError> {
+        conflicts: usize,
+        attempts: AtomicUsize,
+        error: fn() -> E,
+    }
+
+    impl ConflictingUseCase {
+        fn new(conflicts: usize) -> Self {
+            Self::with_error(conflicts, || {
+                ConcurrencyConflict::new("account", 1, 2).into()
+            })
+        }
+    }
+
+    impl<E> ConflictingUseCase<E> {
+        fn with_error(conflicts: usize, error: fn() -> E) -> Self {
+            Self {
+                conflicts,
+                attempts: AtomicUsize::new(0),
+                error,
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl<E> UseCase for ConflictingUseCase<E>
+    where
+        E: Send + 'static,
+    {
+        type Request = ();
+        type Response = Result<usize, E>;
+
+        async fn handle(&self, _: ()) -> Result<usize, E> {
+            let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            if attempt <= self.conflicts {
+                Err((self.error)())
+            } else {
+                Ok(attempt)
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn given_a_concurrency_conflict_when_handling_then_the_request_is_retried() {
+        let use_case = RetryLayer::new(3).layer(ConflictingUseCase::new(2));
+
+        assert_eq!(use_case.handle(()).await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn given_persistent_conflicts_when_handling_then_the_last_error_is_returned() {
+        let use_case = RetryLayer::new(2).layer(ConflictingUseCase::new(5));
+
+        assert!(matches!(
+            use_case.handle(()).await,
+            Err(RepositoryError::ConcurrencyConflict(_))
+        ));
+        assert_eq!(use_case.inner.attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn given_an_application_error_when_handling_then_only_concurrency_conflicts_are_retried()
+    {
+        let use_case = RetryLayer::new(3).layer(ConflictingUseCase::with_error(2, || {
+            ApplicationError::from(ConcurrencyConflict::new("account", 1, 2))
+        }));
+        let conflicting = RetryLayer::new(3).layer(ConflictingUseCase::with_error(2, || {
+            ConflictError::new(ErrorDetail::new("error.account.exists", "Exists")).into()
+        }));
+
+        assert_eq!(use_case.handle(()).await.unwrap(), 3);
+        assert!(matches!(
+            conflicting.handle(()).await,
+            Err(ApplicationError::Conflict(_))
+        ));
+        assert_eq!(conflicting.inner.attempts.load(Ordering::SeqCst), 1);
+    }
+}
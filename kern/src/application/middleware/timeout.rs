@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use crate::application::{
+    error::timeout_error::TimeoutError, middleware::UseCaseLayer, use_case::UseCase,
+};
+
+/// The TimeoutLayer answers a Request with a TimeoutError when the wrapped UseCase does not handle
+/// it within the time limit. The wrapped UseCase is dropped at its next await point, but a write it
+/// already started may still complete, e.g. a statement sent to the database or I/O running on the
+/// blocking thread pool. After a timeout the outcome of an in-flight write is unknown, so the caller
+/// must not assume that nothing was stored
+#[derive(Clone, Copy, Debug)]
+pub struct TimeoutLayer {
+    timeout: Duration,
+}
+
+impl TimeoutLayer {
+    /// Creates a new TimeoutLayer
+    /// # Arguments
+    /// * `timeout` - The time limit of the wrapped UseCase
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl<U> UseCaseLayer<U> for TimeoutLayer {
+    type UseCase = TimeoutUseCase<U>;
+
+    fn layer(&self, inner: U) -> Self::UseCase {
+        TimeoutUseCase {
+            inner,
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// The UseCase created by the TimeoutLayer
+#[derive(Clone, Debug)]
+pub struct TimeoutUseCase<U> {
+    inner: U,
+    timeout: Duration,
+}
+
+#[async_trait::async_trait]
+impl<U, T, E> UseCase for TimeoutUseCase<U>
+where
+    U: UseCase<Response = Result<T, E>>,
+    U::Request: Send + 'static,
+    E: From<TimeoutError>,
+{
+    type Request = U::Request;
+    type Response = U::Response;
+
+    async fn handle(&self, request: U::Request) -> U::Response {
+        tokio::time::timeout(self.timeout, self.inner.handle(request))
+            .await
+            .unwrap_or_else(|_| {
+                Err(TimeoutError::new(std::any::type_name::<U>(), self.timeout).into())
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct SlowUseCase;
+
+    #[async_trait::async_trait]
+    impl UseCase for SlowUseCase {
+        type Request = Duration;
+        type Response = Result<(), TimeoutError>;
+
+        async fn handle(&self, delay: Duration) -> Result<(), TimeoutError> {
+            tokio::time::sleep(delay).await;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn given_a_slow_use_case_when_handling_then_a_timeout_error_is_returned() {
+        let use_case = TimeoutLayer::new(Duration::from_millis(10)).layer(SlowUseCase);
+
+        assert_eq!(use_case.handle(Duration::ZERO).await, Ok(()));
+        assert_eq!(
+            use_case.handle(Duration::from_secs(5)).await,
+            Err(TimeoutError::new(
+                std::any::type_name::<SlowUseCase>(),
+                Duration::from_millis(10)
+            ))
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::{Duration, Instant};
+
+use crate::application::{
+    middleware::{RequestMetadata, UseCaseLayer},
+    request::Request,
+    use_case::UseCase,
+};
+
+/// The TimingLayer measures how long the wrapped UseCase takes to handle a Request and reports it
+/// together with the metadata of the Request
+#[derive(Clone, Debug)]
+pub struct TimingLayer<F> {
+    reporter: F,
+}
+
+impl<F> TimingLayer<F> {
+    /// Creates a new TimingLayer
+    /// # Arguments
+    /// * `reporter` - The function that is called with the metadata of the Request, the type name
+    ///   of the wrapped UseCase and the time it took to handle the Request
+    pub fn new(reporter: F) -> Self {
+        Self { reporter }
+    }
+}
+
+impl<U, F> UseCaseLayer<U> for TimingLayer<F>
+where
+    F: Clone,
+{
+    type UseCase = TimingUseCase<U, F>;
+
+    fn layer(&self, inner: U) -> Self::UseCase {
+        TimingUseCase {
+            inner,
+            reporter: self.reporter.clone(),
+        }
+    }
+}
+
+/// The UseCase created by the TimingLayer
+#[derive(Clone, Debug)]
+pub struct TimingUseCase<U, F> {
+    inner: U,
+    reporter: F,
+}
+
+#[async_trait::async_trait]
+impl<U, F> UseCase for TimingUseCase<U, F>
+where
+    U: UseCase,
+    U::Request: Request + Send + 'static,
+    <U::Request as Request>::RequestId: Send,
+    F: Fn(&RequestMetadata<<U::Request as Request>::RequestId>, &'static str, Duration)
+        + Send
+        + Sync,
+{
+    type Request = U::Request;
+    type Response = U::Response;
+
+    async fn handle(&self, request: U::Request) -> U::Response {
+        let metadata = RequestMetadata::of(&request);
+        let started_at = Instant::now();
+        let response = self.inner.handle(request).await;
+        (self.reporter)(&metadata, std::any::type_name::<U>(), started_at.elapsed());
+        response
+    }
+}
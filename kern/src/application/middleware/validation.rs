@@ -0,0 +1,59 @@
+use crate::{
+    application::{middleware::UseCaseLayer, use_case::UseCase},
+    building_blocks::error::domain_error::DomainError,
+};
+
+/// The ValidationLayer validates every Request before it is handled by the wrapped UseCase. An
+/// invalid Request is answered with the DomainError of the validator and never reaches the
+/// wrapped UseCase
+#[derive(Clone, Debug)]
+pub struct ValidationLayer<F> {
+    validator: F,
+}
+
+impl<F> ValidationLayer<F> {
+    /// Creates a new ValidationLayer
+    /// # Arguments
+    /// * `validator` - The function that validates the Request
+    pub fn new(validator: F) -> Self {
+        Self { validator }
+    }
+}
+
+impl<U, F> UseCaseLayer<U> for ValidationLayer<F>
+where
+    F: Clone,
+{
+    type UseCase = ValidationUseCase<U, F>;
+
+    fn layer(&self, inner: U) -> Self::UseCase {
+        ValidationUseCase {
+            inner,
+            validator: self.validator.clone(),
+        }
+    }
+}
+
+/// The UseCase created by the ValidationLayer
+#[derive(Clone, Debug)]
+pub struct ValidationUseCase<U, F> {
+    inner: U,
+    validator: F,
+}
+
+#[async_trait::async_trait]
+impl<U, F, T, E> UseCase for ValidationUseCase<U, F>
+where
+    U: UseCase<Response = Result<T, E>>,
+    U::Request: Send + 'static,
+    E: From<DomainError>,
+    F: Fn(&U::Request) -> Result<(), DomainError> + Send + Sync,
+{
+    type Request = U::Request;
+    type Response = U::Response;
+
+    async fn handle(&self, request: U::Request) -> U::Response {
+        (self.validator)(&request)?;
+        self.inner.handle(request).await
+    }
+}
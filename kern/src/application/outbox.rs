@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::application::error::outbox_error::OutboxError;
+
+/// An OutboxMessage is a Domain Event waiting in the Outbox to be published
+#[derive(Debug)]
+pub struct OutboxMessage<E> {
+    /// The identifier of the message, ascending in the order the messages were enqueued
+    id: u64,
+    /// The number of failed attempts to publish the message
+    attempts: u32,
+    /// The earliest time the message is published again after a failed attempt
+    retry_at: Option<DateTime<Utc>>,
+    /// The Domain Event
+    event: Arc<E>,
+}
+
+impl<E> OutboxMessage<E> {
+    /// Creates an OutboxMessage
+    /// # Arguments
+    /// * `id` - The identifier of the message
+    /// * `attempts` - The number of failed attempts to publish the message
+    /// * `event` - The Domain Event
+    pub fn new(id: u64, attempts: u32, event: Arc<E>) -> Self {
+        Self {
+            id,
+            attempts,
+            retry_at: None,
+            event,
+        }
+    }
+
+    /// Sets the earliest time the message is published again after a failed attempt
+    /// # Arguments
+    /// * `retry_at` - The earliest time of the next attempt
+    pub fn with_retry_at(mut self, retry_at: Option<DateTime<Utc>>) -> Self {
+        self.retry_at = retry_at;
+        self
+    }
+
+    /// The identifier of the message, ascending in the order the messages were enqueued
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// The number of failed attempts to publish the message
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// The earliest time the message is published again after a failed attempt
+    pub fn retry_at(&self) -> Option<&DateTime<Utc>> {
+        self.retry_at.as_ref()
+    }
+
+    /// Returns true if the message may be published at the time
+    /// # Arguments
+    /// * `now` - The current time
+    pub fn is_due(&self, now: &DateTime<Utc>) -> bool {
+        self.retry_at.is_none_or(|retry_at| retry_at <= *now)
+    }
+
+    /// The Domain Event
+    pub fn event(&self) -> &Arc<E> {
+        &self.event
+    }
+}
+
+impl<E> Clone for OutboxMessage<E> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            attempts: self.attempts,
+            retry_at: self.retry_at,
+            event: self.event.clone(),
+        }
+    }
+}
+
+/// An Outbox stores Domain Events durably until they were published, so a Domain Event is not lost
+/// when the process stops between persisting an Aggregate and publishing its Domain Events.
+///
+/// The Domain Events should be enqueued together with the Aggregate that raised them, ideally in
+/// the same transaction. An OutboxRelay then publishes the pending messages and marks them as
+/// dispatched, retrying the ones that failed after a delay and parking the ones that failed too
+/// often, so a message that cannot be published does not block the messages after it
+#[async_trait::async_trait]
+pub trait Outbox<E>: Send + Sync
+where
+    E: Send + Sync + 'static,
+{
+    /// Enqueues the Domain Events in order
+    /// # Arguments
+    /// * `events` - The Domain Events to enqueue
+    async fn enqueue(&self, events: Vec<E>) -> Result<(), OutboxError>;
+
+    /// The messages that were neither dispatched nor parked yet and whose retry time has passed,
+    /// oldest first
+    /// # Arguments
+    /// * `limit` - The maximum number of messages to return
+    async fn pending(&self, limit: usize) -> Result<Vec<OutboxMessage<E>>, OutboxError>;
+
+    /// Marks the message as dispatched so it is not published again
+    /// # Arguments
+    /// * `id` - The identifier of the message
+    async fn mark_dispatched(&self, id: u64) -> Result<(), OutboxError>;
+
+    /// Records a failed attempt to publish the message. The message stays pending, but it is not
+    /// returned by `pending` before the retry time
+    /// # Arguments
+    /// * `id` - The identifier of the message
+    /// * `retry_at` - The earliest time the message is published again
+    async fn mark_failed(&self, id: u64, retry_at: DateTime<Utc>) -> Result<(), OutboxError>;
+
+    /// Records a failed attempt to publish the message and parks it, so it is not published again
+    /// but kept for inspection, e.g. as a dead letter
+    /// # Arguments
+    /// * `id` - The identifier of the message
+    async fn mark_parked(&self, id: u64) -> Result<(), OutboxError>;
+
+    /// The parked messages, oldest first
+    /// # Arguments
+    /// * `limit` - The maximum number of messages to return
+    async fn parked(&self, limit: usize) -> Result<Vec<OutboxMessage<E>>, OutboxError>;
+}
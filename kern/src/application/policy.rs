@@ -0,0 +1,356 @@
+use std::collections::HashSet;
+
+use crate::application::{
+    error::forbidden_error::ForbiddenError, request::AuthenticatedRequest, role::Role,
+};
+
+/// A Policy is an authorization rule that a request must satisfy before a UseCase handles it.
+///
+/// Any closure that takes the request and returns a `bool` is a Policy, so custom predicates can
+/// be combined with the role based Policies
+///
+/// ```
+/// use kern::application::environment::Environment;
+/// use kern::application::ids::RequestId;
+/// use kern::application::policy::{AllOf, AnyOf, Policy, RequiresAllRoles, RequiresAnyRole};
+/// use kern::application::role::Role;
+/// use kern::building_blocks::ids::UserId;
+/// use chrono::{DateTime, Utc};
+/// use std::collections::HashSet;
+/// use uuid::Uuid;
+///
+/// #[derive(kern::AuthenticatedRequest, Debug)]
+/// pub struct Transfer {
+///     request_id: RequestId,
+///     authorized_party: (),
+///     environment: Environment,
+///     issued_at: DateTime<Utc>,
+///     user_id: UserId<Uuid>,
+///     roles: HashSet<Role>,
+///     amount: u64,
+/// }
+///
+/// impl Transfer {
+///     pub fn new(roles: &[&str], amount: u64) -> Self {
+///         Self {
+///             request_id: RequestId::new_random_v4(),
+///             authorized_party: (),
+///             environment: Environment::Development,
+///             issued_at: Utc::now(),
+///             user_id: UserId::new(Uuid::new_v4()),
+///             roles: roles.iter().map(|role| Role::new(role.to_string())).collect(),
+///             amount,
+///         }
+///     }
+/// }
+///
+/// let policy = AnyOf::new(vec![
+///     Box::new(RequiresAnyRole::new(["admin"])),
+///     Box::new(AllOf::new(vec![
+///         Box::new(RequiresAllRoles::new(["owner", "verified"])),
+///         Box::new(|request: &Transfer| request.amount <= 1_000),
+///     ])),
+/// ]);
+///
+/// assert!(policy.is_satisfied_by(&Transfer::new(&["admin"], 5_000)));
+/// assert!(policy.is_satisfied_by(&Transfer::new(&["owner", "verified"], 100)));
+/// assert!(!policy.is_satisfied_by(&Transfer::new(&["owner", "verified"], 5_000)));
+/// assert!(!policy.is_satisfied_by(&Transfer::new(&["owner"], 100)));
+/// ```
+pub trait Policy<R>: Send + Sync {
+    /// Returns true if the request satisfies the Policy
+    /// # Arguments
+    /// * `request` - The request to authorize
+    fn is_satisfied_by(&self, request: &R) -> bool;
+}
+
+impl<R, F> Policy<R> for F
+where
+    F: Fn(&R) -> bool + Send + Sync,
+{
+    fn is_satisfied_by(&self, request: &R) -> bool {
+        self(request)
+    }
+}
+
+impl<R> Policy<R> for Box<dyn Policy<R>> {
+    fn is_satisfied_by(&self, request: &R) -> bool {
+        self.as_ref().is_satisfied_by(request)
+    }
+}
+
+/// Collects the roles into a set of Roles
+/// # Arguments
+/// * `roles` - The names of the roles
+fn to_roles<I, S>(roles: I) -> HashSet<Role>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    roles
+        .into_iter()
+        .map(|role| Role::new(role.into()))
+        .collect()
+}
+
+/// The RequiresAnyRole Policy is satisfied if the user of the request has at least one of the roles
+#[derive(Clone, Debug)]
+pub struct RequiresAnyRole {
+    roles: HashSet<Role>,
+}
+
+impl RequiresAnyRole {
+    /// Creates a new RequiresAnyRole Policy
+    /// # Arguments
+    /// * `roles` - The roles of which the user must have at least one
+    pub fn new<I, S>(roles: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            roles: to_roles(roles),
+        }
+    }
+}
+
+impl<R> Policy<R> for RequiresAnyRole
+where
+    R: AuthenticatedRequest,
+{
+    fn is_satisfied_by(&self, request: &R) -> bool {
+        !self.roles.is_disjoint(request.roles())
+    }
+}
+
+/// The RequiresAllRoles Policy is satisfied if the user of the request has every one of the roles
+#[derive(Clone, Debug)]
+pub struct RequiresAllRoles {
+    roles: HashSet<Role>,
+}
+
+impl RequiresAllRoles {
+    /// Creates a new RequiresAllRoles Policy
+    /// # Arguments
+    /// * `roles` - The roles the user must all have
+    pub fn new<I, S>(roles: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            roles: to_roles(roles),
+        }
+    }
+}
+
+impl<R> Policy<R> for RequiresAllRoles
+where
+    R: AuthenticatedRequest,
+{
+    fn is_satisfied_by(&self, request: &R) -> bool {
+        self.roles.is_subset(request.roles())
+    }
+}
+
+/// The AnyOf Policy is satisfied if at least one of its Policies is satisfied
+pub struct AnyOf<R> {
+    policies: Vec<Box<dyn Policy<R>>>,
+}
+
+impl<R> AnyOf<R> {
+    /// Creates a new AnyOf Policy
+    /// # Arguments
+    /// * `policies` - The Policies of which at least one must be satisfied
+    pub fn new(policies: Vec<Box<dyn Policy<R>>>) -> Self {
+        Self { policies }
+    }
+}
+
+impl<R> Policy<R> for AnyOf<R> {
+    fn is_satisfied_by(&self, request: &R) -> bool {
+        self.policies
+            .iter()
+            .any(|policy| policy.is_satisfied_by(request))
+    }
+}
+
+/// The AllOf Policy is satisfied if every one of its Policies is satisfied
+pub struct AllOf<R> {
+    policies: Vec<Box<dyn Policy<R>>>,
+}
+
+impl<R> AllOf<R> {
+    /// Creates a new AllOf Policy
+    /// # Arguments
+    /// * `policies` - The Policies that must all be satisfied
+    pub fn new(policies: Vec<Box<dyn Policy<R>>>) -> Self {
+        Self { policies }
+    }
+}
+
+impl<R> Policy<R> for AllOf<R> {
+    fn is_satisfied_by(&self, request: &R) -> bool {
+        self.policies
+            .iter()
+            .all(|policy| policy.is_satisfied_by(request))
+    }
+}
+
+/// A UseCasePolicy attaches a Policy to a UseCase, so a request that does not satisfy the Policy is
+/// rejected with a ForbiddenError with the key `error.<use-case>.forbidden` before the UseCase
+/// handles it.
+///
+/// Role requirements can also be declared next to the UseCase with the `requires_roles` attribute,
+/// which only compiles if the Request of the UseCase is an AuthenticatedRequest
+///
+/// ```compile_fail
+/// use kern::application::{error::forbidden_error::ForbiddenError, use_case::UseCase};
+///
+/// struct Anonymous;
+///
+/// struct PurgeUseCase;
+///
+/// #[kern::requires_roles(any("admin"))]
+/// #[async_trait::async_trait]
+/// impl UseCase for PurgeUseCase {
+///     type Request = Anonymous;
+///     type Response = Result<(), ForbiddenError>;
+///
+///     async fn handle(&self, _: Anonymous) -> Result<(), ForbiddenError> {
+///         Ok(())
+///     }
+/// }
+/// ```
+pub struct UseCasePolicy<P> {
+    /// The name of the UseCase
+    use_case: &'static str,
+    /// The Policy of the UseCase
+    policy: P,
+}
+
+impl<P> UseCasePolicy<P> {
+    /// Creates a new UseCasePolicy
+    /// # Arguments
+    /// * `use_case` - The name of the UseCase that is used in the key of the ForbiddenError
+    /// * `policy` - The Policy a request must satisfy
+    pub fn new(use_case: &'static str, policy: P) -> Self {
+        Self { use_case, policy }
+    }
+
+    /// The name of the UseCase
+    pub fn use_case(&self) -> &'static str {
+        self.use_case
+    }
+
+    /// Returns a ForbiddenError if the request does not satisfy the Policy
+    /// # Arguments
+    /// * `request` - The request to authorize
+    pub fn authorize<R>(&self, request: &R) -> Result<(), ForbiddenError>
+    where
+        P: Policy<R>,
+    {
+        if self.policy.is_satisfied_by(request) {
+            Ok(())
+        } else {
+            Err(ForbiddenError::for_use_case(self.use_case))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{DateTime, Utc};
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{
+        application::{environment::Environment, ids::RequestId, use_case::UseCase},
+        building_blocks::ids::UserId,
+    };
+
+    // The derive macros refer to the crate by its name
+    use crate as kern;
+
+    #[derive(crate::AuthenticatedRequest, Debug)]
+    struct DeleteTenant {
+        request_id: RequestId,
+        authorized_party: (),
+        environment: Environment,
+        issued_at: DateTime<Utc>,
+        user_id: UserId<Uuid>,
+        roles: HashSet<Role>,
+    }
+
+    impl DeleteTenant {
+        fn new<const N: usize>(roles: [&str; N]) -> Self {
+            Self {
+                request_id: RequestId::new_random_v4(),
+                authorized_party: (),
+                environment: Environment::Development,
+                issued_at: Utc::now(),
+                user_id: UserId::new(Uuid::new_v4()),
+                roles: to_roles(roles),
+            }
+        }
+    }
+
+    #[test]
+    fn given_role_policies_when_evaluating_then_any_and_all_semantics_are_applied() {
+        let any = RequiresAnyRole::new(["admin", "owner"]);
+        let all = RequiresAllRoles::new(["admin", "owner"]);
+
+        assert!(any.is_satisfied_by(&DeleteTenant::new(["owner"])));
+        assert!(!any.is_satisfied_by(&DeleteTenant::new(["member"])));
+        assert!(all.is_satisfied_by(&DeleteTenant::new(["admin", "owner", "member"])));
+        assert!(!all.is_satisfied_by(&DeleteTenant::new(["owner"])));
+    }
+
+    #[test]
+    fn given_an_unsatisfied_policy_when_authorizing_then_a_forbidden_error_is_returned() {
+        let policy = UseCasePolicy::new(
+            "delete-tenant",
+            AllOf::new(vec![
+                Box::new(RequiresAnyRole::new(["admin"])),
+                Box::new(|request: &DeleteTenant| request.environment != Environment::Production),
+            ]),
+        );
+
+        assert_eq!(policy.authorize(&DeleteTenant::new(["admin"])), Ok(()));
+        let error = policy.authorize(&DeleteTenant::new(["owner"])).unwrap_err();
+        assert_eq!(error.error_detail().key(), "error.delete-tenant.forbidden");
+    }
+
+    struct DeleteTenantUseCase;
+
+    #[crate::requires_roles(any("admin", "owner"), all("verified"))]
+    #[async_trait::async_trait]
+    impl UseCase for DeleteTenantUseCase {
+        type Request = DeleteTenant;
+        type Response = Result<Environment, ForbiddenError>;
+
+        async fn handle(&self, DeleteTenant { environment, .. }: DeleteTenant) -> Self::Response {
+            Ok(environment)
+        }
+    }
+
+    #[tokio::test]
+    async fn given_a_requires_roles_use_case_when_handling_then_roles_are_checked_first() {
+        let use_case = DeleteTenantUseCase;
+
+        assert_eq!(
+            use_case
+                .handle(DeleteTenant::new(["owner", "verified"]))
+                .await,
+            Ok(Environment::Development)
+        );
+        assert_eq!(
+            use_case.handle(DeleteTenant::new(["owner"])).await,
+            Err(ForbiddenError::for_use_case("delete-tenant"))
+        );
+        assert_eq!(
+            use_case.handle(DeleteTenant::new(["verified"])).await,
+            Err(ForbiddenError::for_use_case("delete-tenant"))
+        );
+    }
+}
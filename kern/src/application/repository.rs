@@ -0,0 +1,35 @@
+use crate::{
+    application::error::repository_error::RepositoryError,
+    building_blocks::{aggregate::Aggregate, entity::Entity},
+};
+
+/// A Repository loads and stores Aggregates as a whole. Writes use optimistic concurrency on
+/// `Aggregate::version`: a write only succeeds when the persisted version still equals the version
+/// the writer loaded, otherwise a `ConcurrencyConflict` is returned.
+///
+/// A version of 0 denotes an Aggregate that has never been persisted
+#[async_trait::async_trait]
+pub trait Repository<A>: Send + Sync
+where
+    A: Aggregate + Entity + Send + Sync,
+{
+    /// Loads the Aggregate with the given identifier, if it exists
+    /// # Arguments
+    /// * `id` - The identifier of the Aggregate
+    async fn find_by_id(&self, id: &A::Id) -> Result<Option<A>, RepositoryError>;
+
+    /// Stores the Aggregate. The current version of the Aggregate is the expected version of the
+    /// persisted Aggregate. The Repository increments the version of the Aggregate after it was
+    /// stored successfully
+    /// # Arguments
+    /// * `aggregate` - The Aggregate to store
+    async fn save(&self, aggregate: &mut A) -> Result<(), RepositoryError>;
+
+    /// Deletes the Aggregate with the given identifier. Deleting an Aggregate that does not exist
+    /// returns a `ConcurrencyConflict` whose actual version is 0, i.e. the version of an Aggregate
+    /// that has never been persisted
+    /// # Arguments
+    /// * `id` - The identifier of the Aggregate
+    /// * `expected_version` - The version the Aggregate is expected to have when it is deleted
+    async fn delete(&self, id: &A::Id, expected_version: u32) -> Result<(), RepositoryError>;
+}
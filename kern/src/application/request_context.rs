@@ -0,0 +1,90 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+
+use crate::application::{
+    environment::Environment,
+    ids::{AuthorizedParty, RequestId},
+    role::Role,
+};
+
+/// The RequestContext holds the metadata and the identity every AuthenticatedRequest carries, so a
+/// request can be built from the context and its own payload.
+///
+/// The `Request` and `AuthenticatedRequest` derives generate a `from_context` constructor that
+/// takes the RequestContext followed by the remaining fields of the request in declaration order. A
+/// Request does not carry the user, so its constructor ignores the `user_id` and `roles`
+///
+/// ```
+/// use kern::application::environment::Environment;
+/// use kern::application::ids::{AuthorizedParty, RequestId};
+/// use kern::application::request::AuthenticatedRequest;
+/// use kern::application::request_context::RequestContext;
+/// use kern::application::role::Role;
+/// use kern::building_blocks::ids::UserId;
+/// use chrono::{DateTime, Utc};
+/// use std::collections::HashSet;
+/// use uuid::Uuid;
+///
+/// #[derive(kern::AuthenticatedRequest, Debug)]
+/// pub struct RenameTenant {
+///     request_id: RequestId,
+///     authorized_party: AuthorizedParty,
+///     environment: Environment,
+///     issued_at: DateTime<Utc>,
+///     user_id: UserId<Uuid>,
+///     roles: HashSet<Role>,
+///     name: String,
+/// }
+///
+/// let context = RequestContext {
+///     request_id: RequestId::new_random_v7(),
+///     authorized_party: AuthorizedParty::new("web-client".to_string()),
+///     environment: Environment::Development,
+///     issued_at: Utc::now(),
+///     user_id: UserId::new(Uuid::new_v4()),
+///     roles: HashSet::from([Role::new("owner".to_string())]),
+/// };
+///
+/// let request = RenameTenant::from_context(context, "tenant.b".to_string());
+///
+/// assert_eq!(request.name, "tenant.b");
+/// assert!(request.roles().contains("owner"));
+///
+/// #[derive(kern::Request, Debug)]
+/// pub struct FindTenant {
+///     request_id: RequestId,
+///     authorized_party: AuthorizedParty,
+///     environment: Environment,
+///     issued_at: DateTime<Utc>,
+///     name: String,
+/// }
+///
+/// let context = RequestContext {
+///     request_id: RequestId::new_random_v7(),
+///     authorized_party: AuthorizedParty::new("web-client".to_string()),
+///     environment: Environment::Development,
+///     issued_at: Utc::now(),
+///     user_id: UserId::new(Uuid::new_v4()),
+///     roles: HashSet::new(),
+/// };
+///
+/// let request = FindTenant::from_context(context, "tenant.a".to_string());
+///
+/// assert_eq!(request.name, "tenant.a");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestContext<U, P = AuthorizedParty> {
+    /// The unique identifier of the request
+    pub request_id: RequestId,
+    /// The identifier of the client that issued the request
+    pub authorized_party: P,
+    /// The environment the request is handled in
+    pub environment: Environment,
+    /// The timestamp of when the request was issued
+    pub issued_at: DateTime<Utc>,
+    /// The identifier of the user that issued the request
+    pub user_id: U,
+    /// The roles of the user
+    pub roles: HashSet<Role>,
+}
@@ -1,31 +1,13 @@
 use std::borrow::Borrow;
 
-use crate::building_blocks::value_object::ValueObject;
+use crate::NewType;
 
 /// A Role is a value object that represents the authorization of a User
-#[derive(Eq, PartialEq, Hash, Clone, Debug)]
+#[derive(Eq, PartialEq, Hash, Clone, Debug, NewType)]
 pub struct Role(String);
 
-impl Role {
-    pub fn new(value: String) -> Self {
-        Self(value)
-    }
-
-    pub fn value(&self) -> &String {
-        &self.0
-    }
-}
-
-impl ValueObject for Role {}
-
 impl Borrow<str> for Role {
     fn borrow(&self) -> &str {
         &self.0
     }
 }
-
-impl Borrow<String> for Role {
-    fn borrow(&self) -> &String {
-        &self.0
-    }
-}
@@ -0,0 +1,301 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{
+        error::{commit_error::CommitError, repository_error::RepositoryError},
+        event::EventPublisher,
+        repository::Repository,
+    },
+    building_blocks::{
+        aggregate::Aggregate, domain_event::DynDomainEvent, entity::Entity,
+        records_events::RecordsEvents,
+    },
+};
+
+/// A TrackedAggregate is an Aggregate together with the Repository it is saved to, so the
+/// UnitOfWork can save Aggregates of different types
+#[async_trait::async_trait]
+trait TrackedAggregate: Send {
+    /// Records the Domain Events again, after they were taken for a save that failed
+    fn restore_events(&mut self, events: Vec<Arc<dyn DynDomainEvent>>);
+
+    /// The topic the Domain Events of the Aggregate are published to
+    fn topic(&self) -> &'static str;
+
+    /// Removes and returns the Domain Events the Aggregate recorded
+    fn take_events(&mut self) -> Vec<Arc<dyn DynDomainEvent>>;
+
+    /// Saves the Aggregate to its Repository
+    async fn save(&mut self) -> Result<(), RepositoryError>;
+}
+
+struct Tracked<'a, A>
+where
+    A: Aggregate + Entity + Send + Sync,
+{
+    aggregate: &'a mut A,
+    repository: Arc<dyn Repository<A>>,
+}
+
+#[async_trait::async_trait]
+impl<A> TrackedAggregate for Tracked<'_, A>
+where
+    A: Aggregate + Entity + RecordsEvents + Send + Sync,
+{
+    fn restore_events(&mut self, events: Vec<Arc<dyn DynDomainEvent>>) {
+        let pending = self.aggregate.pending_events_mut();
+        let recorded = pending.take();
+        for event in events.into_iter().chain(recorded) {
+            pending.push(event);
+        }
+    }
+
+    fn topic(&self) -> &'static str {
+        A::type_name()
+    }
+
+    fn take_events(&mut self) -> Vec<Arc<dyn DynDomainEvent>> {
+        self.aggregate.take_events()
+    }
+
+    async fn save(&mut self) -> Result<(), RepositoryError> {
+        self.repository.save(self.aggregate).await
+    }
+}
+
+/// A UnitOfWork tracks the Aggregates that were changed while handling a single UseCase request.
+///
+/// The UnitOfWork borrows the tracked Aggregates mutably, so once it is committed the caller holds
+/// the saved Aggregates with their new versions. Committing saves every tracked Aggregate in the
+/// order it was tracked, using the optimistic concurrency of its Repository. The Domain Events an
+/// Aggregate recorded are only published once the Aggregate was saved, to the topic named after
+/// `Aggregate::type_name`. Rolling back discards the Domain Events of the tracked Aggregates,
+/// dropping the UnitOfWork leaves them on the Aggregates.
+///
+/// Saves to different Repositories do not share a transaction. If a save fails, the Aggregates
+/// that were already saved keep their Domain Events published and are listed by the CommitError,
+/// while the failed Aggregate and the ones tracked after it are neither saved nor published and
+/// keep their Domain Events. Handlers never see Domain Events of a write that failed
+pub struct UnitOfWork<'a> {
+    publisher: Arc<dyn EventPublisher>,
+    tracked: Vec<Box<dyn TrackedAggregate + 'a>>,
+}
+
+impl<'a> UnitOfWork<'a> {
+    /// Creates a new, empty UnitOfWork
+    /// # Arguments
+    /// * `publisher` - The publisher of the Domain Events after the Aggregates were saved
+    pub fn new(publisher: Arc<dyn EventPublisher>) -> Self {
+        Self {
+            publisher,
+            tracked: Vec::new(),
+        }
+    }
+
+    /// Tracks the changed Aggregate so it is saved when the UnitOfWork is committed
+    /// # Arguments
+    /// * `aggregate` - The changed Aggregate, which holds the saved Aggregate after the commit
+    /// * `repository` - The Repository the Aggregate is saved to
+    pub fn track<A>(&mut self, aggregate: &'a mut A, repository: Arc<dyn Repository<A>>)
+    where
+        A: Aggregate + Entity + RecordsEvents + Send + Sync + 'static,
+    {
+        self.tracked.push(Box::new(Tracked {
+            aggregate,
+            repository,
+        }));
+    }
+
+    /// The number of tracked Aggregates
+    pub fn len(&self) -> usize {
+        self.tracked.len()
+    }
+
+    /// Returns true if no Aggregates are tracked
+    pub fn is_empty(&self) -> bool {
+        self.tracked.is_empty()
+    }
+
+    /// Saves the tracked Aggregates and publishes their Domain Events after they were saved. If a
+    /// save fails, the CommitError lists the Aggregates that were saved before it
+    pub async fn commit(self) -> Result<(), CommitError> {
+        let mut published = Vec::with_capacity(self.tracked.len());
+        let mut result = Ok(());
+
+        for mut tracked in self.tracked {
+            // The events are taken before saving so a stored copy of the Aggregate never holds them
+            let events = tracked.take_events();
+            if let Err(err) = tracked.save().await {
+                tracked.restore_events(events);
+                let committed = published.iter().map(|(topic, _)| *topic).collect();
+                result = Err(CommitError::new(committed, err));
+                break;
+            }
+            published.push((tracked.topic(), events));
+        }
+
+        for (topic, events) in published {
+            self.publisher.publish_all(topic, events);
+        }
+        result
+    }
+
+    /// Discards the Domain Events of the tracked Aggregates without saving them
+    pub fn rollback(self) {
+        for mut tracked in self.tracked {
+            tracked.take_events();
+        }
+    }
+}
+
+#[cfg(all(test, feature = "in_memory"))]
+mod test {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{
+        application::error::application_error::ApplicationError,
+        building_blocks::{ids::EventId, records_events::PendingEvents},
+        infrastructure::persistence::in_memory_repository::InMemoryRepository,
+    };
+    use chrono::{DateTime, Utc};
+    use uuid::Uuid;
+
+    // The derive macros refer to the crate by its name
+    use crate as kern;
+
+    #[derive(crate::Aggregate, Clone, Debug)]
+    struct Account {
+        #[generate_id(Uuid)]
+        #[entity_id]
+        id: AccountId,
+        balance: i64,
+        version: u32,
+        #[pending_events]
+        events: PendingEvents,
+    }
+
+    #[derive(crate::DomainEvent, Debug)]
+    struct Deposited {
+        id: EventId,
+        aggregate_id: AccountId,
+        aggregate_version: u32,
+        occurred_at: DateTime<Utc>,
+    }
+
+    impl Account {
+        fn new() -> Self {
+            Self {
+                id: AccountId::new(Uuid::new_v4()),
+                balance: 0,
+                version: 0,
+                events: PendingEvents::new(),
+            }
+        }
+
+        fn deposit(&mut self, amount: i64) {
+            self.balance += amount;
+            self.record(Deposited {
+                id: EventId::new_random_v4(),
+                aggregate_id: self.id,
+                aggregate_version: self.version + 1,
+                occurred_at: Utc::now(),
+            });
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingPublisher {
+        published: Mutex<Vec<(&'static str, EventId)>>,
+    }
+
+    impl EventPublisher for RecordingPublisher {
+        fn publish(&self, topic: &'static str, event: Arc<dyn DynDomainEvent>) {
+            self.published.lock().unwrap().push((topic, *event.id()));
+        }
+    }
+
+    #[tokio::test]
+    async fn given_tracked_aggregates_when_committing_then_events_are_published_after_saving() {
+        let publisher = Arc::new(RecordingPublisher::default());
+        let repository = Arc::new(InMemoryRepository::<Account>::new());
+        let mut account = Account::new();
+        account.deposit(10);
+        let id = account.id;
+
+        let mut unit_of_work = UnitOfWork::new(publisher.clone());
+        unit_of_work.track(&mut account, repository.clone());
+        unit_of_work.commit().await.unwrap();
+
+        let stored = repository.find_by_id(&id).await.unwrap().unwrap();
+        assert_eq!(account.version(), 1);
+        assert!(account.pending_events().is_empty());
+        assert_eq!(stored.version(), 1);
+        assert!(stored.pending_events().is_empty());
+        assert_eq!(publisher.published.lock().unwrap()[0].0, "account");
+    }
+
+    #[tokio::test]
+    async fn given_a_conflicting_aggregate_when_committing_then_its_events_are_not_published() {
+        let publisher = Arc::new(RecordingPublisher::default());
+        let repository = Arc::new(InMemoryRepository::<Account>::new());
+        let mut account = Account::new();
+        let mut stale = account.clone();
+        repository.save(&mut account).await.unwrap();
+
+        stale.deposit(10);
+        let mut unit_of_work = UnitOfWork::new(publisher.clone());
+        unit_of_work.track(&mut stale, repository.clone());
+        let err = unit_of_work.commit().await.unwrap_err();
+
+        assert!(!err.is_partial());
+        assert!(matches!(
+            err.error(),
+            RepositoryError::ConcurrencyConflict(_)
+        ));
+        assert!(publisher.published.lock().unwrap().is_empty());
+        assert_eq!(stale.pending_events().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn given_a_failure_after_a_save_when_committing_then_the_saved_aggregates_are_listed() {
+        let publisher = Arc::new(RecordingPublisher::default());
+        let repository = Arc::new(InMemoryRepository::<Account>::new());
+        let mut saved = Account::new();
+        saved.deposit(10);
+        let mut account = Account::new();
+        let mut stale = account.clone();
+        repository.save(&mut account).await.unwrap();
+        stale.deposit(5);
+
+        let mut unit_of_work = UnitOfWork::new(publisher.clone());
+        unit_of_work.track(&mut saved, repository.clone());
+        unit_of_work.track(&mut stale, repository.clone());
+        let err = unit_of_work.commit().await.unwrap_err();
+
+        assert!(err.is_partial());
+        assert_eq!(err.committed(), ["account"]);
+        assert_eq!(saved.version(), 1);
+        assert_eq!(publisher.published.lock().unwrap().len(), 1);
+        assert!(matches!(
+            ApplicationError::from(err),
+            ApplicationError::Conflict(conflict) if conflict.error_detail().key() == "error.unit-of-work.partial-commit"
+        ));
+    }
+
+    #[tokio::test]
+    async fn given_tracked_aggregates_when_rolling_back_then_nothing_is_saved() {
+        let publisher = Arc::new(RecordingPublisher::default());
+        let repository = Arc::new(InMemoryRepository::<Account>::new());
+        let mut account = Account::new();
+        account.deposit(10);
+
+        let mut unit_of_work = UnitOfWork::new(publisher.clone());
+        unit_of_work.track(&mut account, repository.clone());
+        unit_of_work.rollback();
+
+        assert!(repository.is_empty());
+        assert!(account.pending_events().is_empty());
+        assert!(publisher.published.lock().unwrap().is_empty());
+    }
+}
@@ -2,5 +2,8 @@ pub mod aggregate;
 pub mod domain_event;
 pub mod entity;
 pub mod error;
+pub mod event_sourced;
 pub mod ids;
+pub mod records_events;
+pub mod type_name;
 pub mod value_object;
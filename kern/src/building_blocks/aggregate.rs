@@ -1,3 +1,5 @@
+use crate::building_blocks::{error::domain_error::DomainError, type_name::TypeName};
+
 /// An Aggregate is a cluster of domain objects (entities and value objects) that are treated as a
 /// single unit.
 ///
@@ -5,6 +7,7 @@
 /// use kern::Aggregate;
 /// use kern::building_blocks::aggregate::Aggregate;
 /// use kern::building_blocks::entity::Entity;
+/// use kern::building_blocks::type_name::TypeName;
 /// use uuid::Uuid;
 ///
 /// #[derive(kern::Aggregate, Debug)]
@@ -37,13 +40,122 @@
 /// assert_eq!(Tenant::type_name(), "tenant");
 /// assert_eq!(a.version(), 1);
 /// assert_eq!(b.version(), 2);
+///
+/// let mut a = a;
+/// a.increment_version().unwrap();
+/// assert_eq!(a.version(), 2);
 /// ```
 ///
-pub trait Aggregate {
+/// The options of `generate_id` add the capabilities that the id needs, e.g. to be part of a URL
+/// or a JSON document
+///
+/// ```
+/// use kern::building_blocks::entity::Entity;
+/// use uuid::Uuid;
+///
+/// #[derive(kern::Aggregate, Debug)]
+/// pub struct Invoice {
+///     #[generate_id(Uuid, display, from_str, serde, v7, ord)]
+///     #[entity_id]
+///     id: InvoiceId,
+///     version: u32,
+/// }
+///
+/// let id = InvoiceId::new_random();
+/// assert_eq!(id.value().get_version_num(), 7);
+/// assert_eq!(id.to_string().parse::<InvoiceId>(), Ok(id));
+/// assert_eq!(serde_json::to_string(&id).unwrap(), format!("\"{id}\""));
+/// assert!(id < InvoiceId::new_random());
+/// assert!(InvoiceId::try_from("INV-1").is_err());
+/// ```
+pub trait Aggregate: TypeName {
     /// The current version of the Aggregate. Whenever the Aggregate changes, the version should be
     /// incremented to reflect an update has occured. The persistence layer should be the one
     /// responsible for incrementing an Aggregate's version
     fn version(&self) -> u32;
 
-    fn type_name() -> &'static str;
+    /// Increments the version of the Aggregate by one. Only the persistence layer should call this
+    /// after it successfully stored the Aggregate. Returns `VERSION_OVERFLOW` when the version is
+    /// already `u32::MAX`
+    fn increment_version(&mut self) -> Result<(), DomainError>;
+}
+
+crate::declare_errors! {
+    /// The version of the Aggregate has reached `u32::MAX`
+    pub VERSION_OVERFLOW = "error.aggregate.version-overflow" => "The version of the aggregate cannot be incremented any further";
+}
+
+#[cfg(test)]
+mod test {
+    use crate::building_blocks::{entity::Entity, error::domain_error::DomainError};
+    use uuid::Uuid;
+
+    #[derive(crate::Aggregate, Debug)]
+    struct PurchaseOrder {
+        #[generate_id(Uuid, from_str, serde, schema)]
+        #[entity_id]
+        id: PurchaseOrderId,
+        #[field]
+        version: u32,
+    }
+
+    #[test]
+    fn given_the_maximum_version_when_incrementing_then_the_version_overflows() {
+        use crate::building_blocks::aggregate::{Aggregate, VERSION_OVERFLOW};
+
+        let mut order = PurchaseOrder {
+            id: PurchaseOrderId::new(Uuid::now_v7()),
+            version: u32::MAX - 1,
+        };
+
+        assert_eq!(order.increment_version(), Ok(()));
+        assert_eq!(order.increment_version(), Err(VERSION_OVERFLOW.into()));
+        assert_eq!(Aggregate::version(&order), u32::MAX);
+    }
+
+    #[test]
+    fn given_an_invalid_id_when_parsing_then_the_error_has_the_prefix_of_the_aggregate() {
+        let error = "42".parse::<PurchaseOrderId>().unwrap_err();
+
+        assert!(matches!(
+            error,
+            DomainError::Single { error_detail }
+                if error_detail.key() == "error.purchase-order.invalid-id"
+                    && error_detail.params()["code"] == "format"
+        ));
+    }
+
+    #[test]
+    fn given_an_id_with_serde_when_serializing_then_it_is_the_wrapped_value() {
+        let uuid = Uuid::now_v7();
+        let order = PurchaseOrder {
+            id: PurchaseOrderId::new(uuid),
+            version: 1,
+        };
+
+        let json = serde_json::to_value(order.id()).unwrap();
+        assert_eq!(json, serde_json::json!(uuid.to_string()));
+        assert_eq!(
+            serde_json::from_value::<PurchaseOrderId>(json).unwrap(),
+            PurchaseOrderId::try_from(uuid.to_string().as_str()).unwrap()
+        );
+        assert!(serde_json::from_str::<PurchaseOrderId>("7").is_err());
+    }
+
+    #[test]
+    fn given_an_id_with_a_schema_when_documenting_then_it_is_a_uuid_string() {
+        use utoipa::{
+            PartialSchema, ToSchema,
+            openapi::schema::{KnownFormat, ObjectBuilder, SchemaFormat, Type},
+        };
+
+        assert_eq!(PurchaseOrderId::name(), "PurchaseOrderId");
+        assert!(
+            PurchaseOrderId::schema()
+                == ObjectBuilder::new()
+                    .schema_type(Type::String)
+                    .format(Some(SchemaFormat::KnownFormat(KnownFormat::Uuid)))
+                    .into()
+        );
+    }
 }
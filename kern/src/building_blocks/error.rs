@@ -1,2 +1,34 @@
 pub mod domain_error;
+#[cfg(feature = "error_catalog")]
+pub mod error_catalog;
 pub mod error_detail;
+pub mod error_details;
+pub mod validation;
+
+/// Registers an ErrorKey in the ErrorCatalog. Used by `declare_errors!` and by the derives that
+/// generate error keys
+#[cfg(feature = "error_catalog")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_error_key {
+    ($key:literal, $message:literal, $name:literal, $module:expr) => {
+        const _: () = {
+            #[$crate::linkme::distributed_slice(
+                $crate::building_blocks::error::error_catalog::ERROR_KEYS
+            )]
+            #[linkme(crate = $crate::linkme)]
+            static ERROR_KEY: $crate::building_blocks::error::error_catalog::ErrorKey =
+                $crate::building_blocks::error::error_catalog::ErrorKey::new(
+                    $key, $message, $name, $module,
+                );
+        };
+    };
+}
+
+/// The ErrorCatalog is disabled, so the ErrorKey is not registered
+#[cfg(not(feature = "error_catalog"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_error_key {
+    ($key:literal, $message:literal, $name:literal, $module:expr) => {};
+}
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::building_blocks::error::error_detail::ErrorDetail;
+use crate::building_blocks::error::{error_detail::ErrorDetail, error_details::ErrorDetails};
 
 /// A DomainError is any error that is a violation in the business rules/invariant
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -10,8 +10,8 @@ pub enum DomainError {
         error_detail: ErrorDetail,
     },
     Multiple {
-        /// The descriptions of the errors
-        error_details: HashSet<ErrorDetail>,
+        /// The descriptions of the errors, ordered by key
+        error_details: ErrorDetails,
     },
 }
 
@@ -26,17 +26,39 @@ impl DomainError {
     /// Creates a DomainError::Multiple
     /// # Arguments
     /// * `error_details` - The details describing the DomainError
-    pub fn multiple(error_details: HashSet<ErrorDetail>) -> Self {
-        Self::Multiple { error_details }
+    pub fn multiple<D>(error_details: D) -> Self
+    where
+        D: Into<ErrorDetails>,
+    {
+        Self::Multiple {
+            error_details: error_details.into(),
+        }
     }
 
-    /// The ErrorDetail describing the DomainError
+    /// The ErrorDetails describing the DomainError, ordered by key
     pub fn error_details(&self) -> Box<dyn Iterator<Item = &ErrorDetail> + '_> {
         match self {
             Self::Single { error_detail } => Box::new(std::iter::once(error_detail)),
             Self::Multiple { error_details } => Box::new(error_details.iter()),
         }
     }
+
+    /// Consumes the DomainError and returns its ErrorDetails
+    pub fn into_error_details(self) -> ErrorDetails {
+        match self {
+            Self::Single { error_detail } => ErrorDetails::from([error_detail]),
+            Self::Multiple { error_details } => error_details,
+        }
+    }
+
+    /// Merges two DomainErrors into one that contains the ErrorDetails of both
+    /// # Arguments
+    /// * `other` - The DomainError to merge into this one
+    pub fn merge(self, other: DomainError) -> Self {
+        let mut error_details = self.into_error_details();
+        error_details.extend(other.into_error_details());
+        error_details.into()
+    }
 }
 
 impl From<ErrorDetail> for DomainError {
@@ -45,8 +67,8 @@ impl From<ErrorDetail> for DomainError {
     }
 }
 
-impl From<HashSet<ErrorDetail>> for DomainError {
-    fn from(value: HashSet<ErrorDetail>) -> Self {
+impl From<ErrorDetails> for DomainError {
+    fn from(value: ErrorDetails) -> Self {
         if value.len() == 1 {
             value.into_iter().next().unwrap().into()
         } else {
@@ -55,6 +77,12 @@ impl From<HashSet<ErrorDetail>> for DomainError {
     }
 }
 
+impl From<HashSet<ErrorDetail>> for DomainError {
+    fn from(value: HashSet<ErrorDetail>) -> Self {
+        ErrorDetails::from(value).into()
+    }
+}
+
 impl std::fmt::Display for DomainError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut iter = self.error_details().peekable();
@@ -73,3 +101,30 @@ impl std::fmt::Display for DomainError {
 }
 
 impl std::error::Error for DomainError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn given_errors_with_the_same_key_when_merging_then_every_message_is_kept() {
+        let too_long: DomainError =
+            ErrorDetail::new("error.tenant.invalid-name", "The name is too long").into();
+        let digits: DomainError =
+            ErrorDetail::new("error.tenant.invalid-name", "The name contains digits").into();
+
+        let error = too_long.merge(digits);
+
+        assert!(matches!(error, DomainError::Multiple { .. }));
+        assert_eq!(
+            error.to_string(),
+            "error.tenant.invalid-name: The name is too long; \
+             error.tenant.invalid-name: The name contains digits"
+        );
+        assert!(
+            error
+                .into_error_details()
+                .contains("error.tenant.invalid-name")
+        );
+    }
+}
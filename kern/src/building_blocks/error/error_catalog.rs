@@ -0,0 +1,239 @@
+use linkme::distributed_slice;
+
+use crate::building_blocks::error::error_detail::ErrorDetail;
+
+/// The ErrorKeys declared with `declare_errors!` in every crate of the program
+#[distributed_slice]
+pub static ERROR_KEYS: [ErrorKey];
+
+/// An ErrorKey is the registration of an ErrorDetail constant declared with `declare_errors!`, or
+/// of a key that a derive generates for a type, e.g. `error.<aggregate>.concurrency-conflict`
+#[derive(Debug, serde::Serialize)]
+pub struct ErrorKey {
+    /// The key of the error
+    key: &'static str,
+    /// The descriptive message of the error
+    message: &'static str,
+    /// The name of the constant, or of the type a generated key belongs to
+    name: &'static str,
+    /// The module the constant is declared in
+    module: &'static str,
+}
+
+impl ErrorKey {
+    /// Creates an ErrorKey
+    /// # Arguments
+    /// * `key` - The key of the error
+    /// * `message` - The descriptive message of the error
+    /// * `name` - The name of the constant, or of the type a generated key belongs to
+    /// * `module` - The module the constant is declared in
+    pub const fn new(
+        key: &'static str,
+        message: &'static str,
+        name: &'static str,
+        module: &'static str,
+    ) -> Self {
+        Self {
+            key,
+            message,
+            name,
+            module,
+        }
+    }
+
+    /// The key of the error
+    pub fn key(&self) -> &'static str {
+        self.key
+    }
+
+    /// The descriptive message of the error
+    pub fn message(&self) -> &'static str {
+        self.message
+    }
+
+    /// The name of the constant, or of the type a generated key belongs to
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The module the constant is declared in
+    pub fn module(&self) -> &'static str {
+        self.module
+    }
+
+    /// The ErrorDetail of the ErrorKey
+    pub fn error_detail(&self) -> ErrorDetail {
+        ErrorDetail::new_const(self.key, self.message)
+    }
+}
+
+/// The ErrorCatalog lists every error key declared with `declare_errors!` and the keys that the
+/// `Aggregate` derive, `generate_id` and `requires_roles` generate, ordered by key, and exports
+/// them for the consumers of an API
+///
+/// ```
+/// use kern::building_blocks::error::error_catalog::ErrorCatalog;
+///
+/// kern::declare_errors! {
+///     /// The tenant has no name
+///     pub INVALID_TENANT_NAME = "error.tenant.invalid-name" => "The name of the tenant is empty";
+/// }
+///
+/// let catalog = ErrorCatalog::collect();
+///
+/// assert_eq!(catalog.get("error.tenant.invalid-name").unwrap().name(), "INVALID_TENANT_NAME");
+/// assert!(catalog.to_markdown().contains("| `error.tenant.invalid-name` |"));
+/// ```
+///
+/// Keys must have the format `error.<type>.<slug>`
+///
+/// ```compile_fail
+/// kern::declare_errors! {
+///     pub INVALID_NAME = "tenant.invalid_name" => "The name of the tenant is empty";
+/// }
+/// ```
+///
+/// and a key cannot be declared twice
+///
+/// ```compile_fail
+/// kern::declare_errors! {
+///     pub INVALID_NAME = "error.tenant.invalid-name" => "The name of the tenant is empty";
+/// }
+///
+/// mod other {
+///     kern::declare_errors! {
+///         pub EMPTY_NAME = "error.tenant.invalid-name" => "The name of the tenant is empty";
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ErrorCatalog {
+    /// The declared ErrorKeys, ordered by key
+    error_keys: Vec<&'static ErrorKey>,
+}
+
+impl ErrorCatalog {
+    /// Collects the ErrorKeys declared in every crate of the program
+    pub fn collect() -> Self {
+        let mut error_keys: Vec<&'static ErrorKey> = ERROR_KEYS.iter().collect();
+        error_keys.sort_by_key(|error_key| error_key.key);
+        // Types that share an error prefix register the same generated key
+        error_keys.dedup_by_key(|error_key| error_key.key);
+        Self { error_keys }
+    }
+
+    /// The declared ErrorKeys, ordered by key
+    pub fn error_keys(&self) -> &[&'static ErrorKey] {
+        &self.error_keys
+    }
+
+    /// Looks up an ErrorKey
+    /// # Arguments
+    /// * `key` - The key of the error
+    pub fn get(&self, key: &str) -> Option<&'static ErrorKey> {
+        self.error_keys
+            .binary_search_by_key(&key, |error_key| error_key.key)
+            .ok()
+            .map(|index| self.error_keys[index])
+    }
+
+    /// Exports the catalog as a JSON array of `key`, `message`, `name` and `module` objects
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.error_keys).expect("ErrorKeys are serializable")
+    }
+
+    /// Exports the catalog as a Markdown table
+    pub fn to_markdown(&self) -> String {
+        let escape = |text: &str| text.replace('|', "\\|");
+        let mut markdown = String::from("| Key | Message | Declared in |\n| --- | --- | --- |\n");
+        for error_key in &self.error_keys {
+            markdown.push_str(&format!(
+                "| `{}` | {} | `{}::{}` |\n",
+                error_key.key,
+                escape(error_key.message),
+                error_key.module,
+                error_key.name
+            ));
+        }
+        markdown
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        application::error::concurrency_conflict::ConcurrencyConflict,
+        building_blocks::{entity::Entity, type_name::TypeName},
+    };
+
+    crate::declare_errors! {
+        /// A test error
+        pub(crate) TEST_ERROR = "error.catalog-test.failed" => "The test failed | badly";
+    }
+
+    #[test]
+    fn given_declared_errors_when_collecting_then_the_catalog_contains_them() {
+        let catalog = ErrorCatalog::collect();
+
+        assert_eq!(TEST_ERROR.key(), "error.catalog-test.failed");
+        let error_key = catalog.get("error.catalog-test.failed").unwrap();
+        assert_eq!(error_key.name(), "TEST_ERROR");
+        assert_eq!(error_key.module(), module_path!());
+        assert_eq!(error_key.error_detail(), TEST_ERROR);
+        assert!(catalog.get("error.request.timeout").is_some());
+        assert!(
+            catalog
+                .error_keys()
+                .windows(2)
+                .all(|pair| pair[0].key() < pair[1].key())
+        );
+    }
+
+    #[derive(crate::Aggregate, Debug)]
+    struct CatalogTestOrder {
+        #[generate_id(uuid::Uuid, from_str)]
+        #[entity_id]
+        id: CatalogTestOrderId,
+        version: u32,
+    }
+
+    #[test]
+    fn given_an_aggregate_when_collecting_then_the_catalog_contains_its_generated_keys() {
+        let catalog = ErrorCatalog::collect();
+        let order = CatalogTestOrder {
+            id: CatalogTestOrderId::new(uuid::Uuid::nil()),
+            version: 1,
+        };
+        let conflict = ConcurrencyConflict::new(CatalogTestOrder::error_prefix(), 0, order.version)
+            .error_detail();
+
+        let error_key = catalog.get(conflict.key()).unwrap();
+        assert_eq!(
+            error_key.key(),
+            "error.catalog-test-order.concurrency-conflict"
+        );
+        assert_eq!(error_key.name(), "CatalogTestOrder");
+        assert_eq!(error_key.module(), module_path!());
+        assert_eq!(
+            catalog
+                .get("error.catalog-test-order.invalid-id")
+                .unwrap()
+                .name(),
+            "CatalogTestOrderId"
+        );
+        #[cfg(feature = "jwt")]
+        assert!(catalog.get("error.authentication.invalid-claims").is_some());
+    }
+
+    #[test]
+    fn given_a_catalog_when_exporting_then_json_and_markdown_list_every_key() {
+        let catalog = ErrorCatalog::collect();
+
+        let json: serde_json::Value = serde_json::from_str(&catalog.to_json()).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), catalog.error_keys().len());
+        assert!(catalog.to_markdown().contains(
+            "| `error.catalog-test.failed` | The test failed \\| badly | `kern::building_blocks::error::error_catalog::test::TEST_ERROR` |"
+        ));
+    }
+}
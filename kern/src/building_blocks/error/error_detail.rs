@@ -1,15 +1,22 @@
 use std::{
     borrow::{Borrow, Cow},
+    collections::BTreeMap,
     hash::Hash,
 };
 
-/// An ErrorDetail represents a key value pair that describes an error
+/// An ErrorDetail represents a key value pair that describes an error. It can additionally point
+/// to the offending field and carry the named parameters of the error, so a client can highlight
+/// the input and render its own localized message
 #[derive(Debug, Clone)]
 pub struct ErrorDetail {
     /// The key of the error
     key: Cow<'static, str>,
     /// The descriptive message of the error
     message: Cow<'static, str>,
+    /// The JSON pointer to the offending field, e.g. `/address/zip`
+    field: Option<Cow<'static, str>>,
+    /// The named parameters of the error, e.g. `min`, `max` or `value`
+    params: BTreeMap<Cow<'static, str>, serde_json::Value>,
 }
 
 impl ErrorDetail {
@@ -25,9 +32,46 @@ impl ErrorDetail {
         ErrorDetail {
             key: key.into(),
             message: message.into(),
+            field: None,
+            params: BTreeMap::new(),
         }
     }
 
+    /// Replaces the descriptive message, e.g. with a localized one
+    /// # Arguments
+    /// * `message` - The descriptive error message
+    pub fn with_message<M>(mut self, message: M) -> Self
+    where
+        M: Into<Cow<'static, str>>,
+    {
+        self.message = message.into();
+        self
+    }
+
+    /// Sets the field the error is about
+    /// # Arguments
+    /// * `field` - The JSON pointer to the offending field, e.g. `/address/zip`
+    pub fn with_field<F>(mut self, field: F) -> Self
+    where
+        F: Into<Cow<'static, str>>,
+    {
+        self.field = Some(field.into());
+        self
+    }
+
+    /// Adds a named parameter to the error, replacing a parameter with the same name
+    /// # Arguments
+    /// * `name` - The name of the parameter, e.g. `min`
+    /// * `value` - The value of the parameter
+    pub fn with_param<N, V>(mut self, name: N, value: V) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        V: Into<serde_json::Value>,
+    {
+        self.params.insert(name.into(), value.into());
+        self
+    }
+
     /// The error key
     pub fn key(&self) -> &str {
         &self.key
@@ -38,6 +82,16 @@ impl ErrorDetail {
         &self.message
     }
 
+    /// The JSON pointer to the offending field, if the error is about a field
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+
+    /// The named parameters of the error, ordered by name
+    pub fn params(&self) -> &BTreeMap<Cow<'static, str>, serde_json::Value> {
+        &self.params
+    }
+
     /// Creates an ErrorDetail.
     /// # Arguments
     /// * `key` - The error key
@@ -46,6 +100,8 @@ impl ErrorDetail {
         ErrorDetail {
             key: Cow::Borrowed(key),
             message: Cow::Borrowed(message),
+            field: None,
+            params: BTreeMap::new(),
         }
     }
 }
@@ -94,4 +150,24 @@ mod test {
         details.insert(detail);
         assert!(details.contains("error.user.invalid-name"))
     }
+
+    #[test]
+    fn given_a_field_and_params_when_creating_an_error_detail_then_both_are_kept() {
+        let detail = ErrorDetail::new("error.user.invalid-age", "Too young")
+            .with_field("/person/age")
+            .with_param("min", 18)
+            .with_param("value", 16);
+
+        assert_eq!(detail.field(), Some("/person/age"));
+        assert_eq!(
+            detail
+                .params()
+                .keys()
+                .map(|name| name.as_ref())
+                .collect::<Vec<_>>(),
+            vec!["min", "value"]
+        );
+        assert_eq!(detail.params()["min"], 18);
+        assert_eq!(detail, ErrorDetail::new("error.user.invalid-age", "Other"));
+    }
 }
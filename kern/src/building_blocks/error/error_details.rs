@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+
+use crate::building_blocks::error::error_detail::ErrorDetail;
+
+/// ErrorDetails is an ordered multimap of ErrorDetails by their key. Unlike a
+/// `HashSet<ErrorDetail>` it keeps every ErrorDetail of a key that differs in its message or field,
+/// e.g. the length and the pattern violation of the same field. The ErrorDetails are ordered by
+/// key and, within a key, by insertion, so they are always rendered in the same order
+///
+/// ```
+/// use kern::building_blocks::error::error_detail::ErrorDetail;
+/// use kern::building_blocks::error::error_details::ErrorDetails;
+///
+/// let details: ErrorDetails = [
+///     ErrorDetail::new("error.tenant.invalid-name", "The name is too long"),
+///     ErrorDetail::new("error.tenant.invalid-code", "The code is empty"),
+///     ErrorDetail::new("error.tenant.invalid-name", "The name contains digits"),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// assert!(details.contains("error.tenant.invalid-name"));
+/// assert_eq!(details.get("error.tenant.invalid-name").len(), 2);
+/// assert_eq!(
+///     details.iter().map(|detail| detail.message()).collect::<Vec<_>>(),
+///     vec!["The code is empty", "The name is too long", "The name contains digits"]
+/// );
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ErrorDetails {
+    /// The ErrorDetails, ordered by key and, within a key, by insertion
+    details: Vec<ErrorDetail>,
+}
+
+impl ErrorDetails {
+    /// Creates empty ErrorDetails
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an ErrorDetail after the ErrorDetails with the same key. An ErrorDetail with the same
+    /// key, message and field as an existing one is not added again
+    /// # Arguments
+    /// * `detail` - The ErrorDetail to add
+    pub fn insert(&mut self, detail: ErrorDetail) -> bool {
+        if self.get(detail.key()).iter().any(|existing| {
+            existing.message() == detail.message() && existing.field() == detail.field()
+        }) {
+            return false;
+        }
+        let index = self
+            .details
+            .partition_point(|existing| existing.key() <= detail.key());
+        self.details.insert(index, detail);
+        true
+    }
+
+    /// Returns true if there is an ErrorDetail with the key
+    /// # Arguments
+    /// * `key` - The error key
+    pub fn contains(&self, key: &str) -> bool {
+        !self.get(key).is_empty()
+    }
+
+    /// The ErrorDetails with the key, in insertion order
+    /// # Arguments
+    /// * `key` - The error key
+    pub fn get(&self, key: &str) -> &[ErrorDetail] {
+        let start = self.details.partition_point(|detail| detail.key() < key);
+        let end = self.details.partition_point(|detail| detail.key() <= key);
+        &self.details[start..end]
+    }
+
+    /// The number of ErrorDetails
+    pub fn len(&self) -> usize {
+        self.details.len()
+    }
+
+    /// Returns true if there are no ErrorDetails
+    pub fn is_empty(&self) -> bool {
+        self.details.is_empty()
+    }
+
+    /// The ErrorDetails, ordered by key and, within a key, by insertion
+    pub fn iter(&self) -> std::slice::Iter<'_, ErrorDetail> {
+        self.details.iter()
+    }
+}
+
+impl Extend<ErrorDetail> for ErrorDetails {
+    fn extend<I: IntoIterator<Item = ErrorDetail>>(&mut self, iter: I) {
+        for detail in iter {
+            self.insert(detail);
+        }
+    }
+}
+
+impl FromIterator<ErrorDetail> for ErrorDetails {
+    fn from_iter<I: IntoIterator<Item = ErrorDetail>>(iter: I) -> Self {
+        let mut details = Self::new();
+        details.extend(iter);
+        details
+    }
+}
+
+impl From<HashSet<ErrorDetail>> for ErrorDetails {
+    fn from(value: HashSet<ErrorDetail>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<const N: usize> From<[ErrorDetail; N]> for ErrorDetails {
+    fn from(value: [ErrorDetail; N]) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl IntoIterator for ErrorDetails {
+    type Item = ErrorDetail;
+    type IntoIter = std::vec::IntoIter<ErrorDetail>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.details.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ErrorDetails {
+    type Item = &'a ErrorDetail;
+    type IntoIter = std::slice::Iter<'a, ErrorDetail>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.details.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn given_details_with_the_same_key_when_inserting_then_distinct_ones_are_kept_in_order() {
+        let mut details = ErrorDetails::new();
+
+        assert!(details.insert(ErrorDetail::new("error.user.invalid-name", "Too long")));
+        assert!(details.insert(ErrorDetail::new("error.user.invalid-age", "Too young")));
+        assert!(details.insert(ErrorDetail::new("error.user.invalid-name", "Has digits")));
+        assert!(!details.insert(ErrorDetail::new("error.user.invalid-name", "Too long")));
+        assert!(
+            details.insert(
+                ErrorDetail::new("error.user.invalid-name", "Too long").with_field("/alias")
+            )
+        );
+
+        assert_eq!(details.len(), 4);
+        assert!(details.contains("error.user.invalid-age"));
+        assert!(!details.contains("error.user.invalid"));
+        assert_eq!(
+            details
+                .iter()
+                .map(|detail| (detail.key(), detail.message()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("error.user.invalid-age", "Too young"),
+                ("error.user.invalid-name", "Too long"),
+                ("error.user.invalid-name", "Has digits"),
+                ("error.user.invalid-name", "Too long"),
+            ]
+        );
+    }
+}
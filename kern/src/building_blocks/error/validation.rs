@@ -0,0 +1,224 @@
+use crate::building_blocks::error::{
+    domain_error::DomainError, error_detail::ErrorDetail, error_details::ErrorDetails,
+};
+
+/// Validated combines the results of several validations and accumulates the errors of all of
+/// them, instead of stopping at the first one like `Result::and` and the `?` operator do
+///
+/// ```
+/// use kern::building_blocks::error::domain_error::DomainError;
+/// use kern::building_blocks::error::error_detail::ErrorDetail;
+/// use kern::building_blocks::error::validation::Validated;
+///
+/// fn name(name: &str) -> Result<String, DomainError> {
+///     if name.is_empty() {
+///         return Err(ErrorDetail::new("error.tenant.invalid-name", "The name is empty").into());
+///     }
+///     Ok(name.to_string())
+/// }
+///
+/// fn seats(seats: u32) -> Result<u32, DomainError> {
+///     if seats == 0 {
+///         return Err(ErrorDetail::new("error.tenant.invalid-seats", "No seats").into());
+///     }
+///     Ok(seats)
+/// }
+///
+/// assert_eq!(name("acme").zip(seats(5)), Ok(("acme".to_string(), 5)));
+///
+/// let error = name("").zip(seats(0)).unwrap_err();
+/// assert!(matches!(error, DomainError::Multiple { .. }));
+/// assert_eq!(error.error_details().count(), 2);
+/// ```
+pub trait Validated<T> {
+    /// Combines two results into a result of both values, or of the errors of both
+    /// # Arguments
+    /// * `other` - The other result
+    fn zip<U>(self, other: Result<U, DomainError>) -> Result<(T, U), DomainError>;
+
+    /// Returns the other result if both results are successful, or the errors of both
+    /// # Arguments
+    /// * `other` - The other result
+    fn and_also<U>(self, other: Result<U, DomainError>) -> Result<U, DomainError>;
+}
+
+impl<T> Validated<T> for Result<T, DomainError> {
+    fn zip<U>(self, other: Result<U, DomainError>) -> Result<(T, U), DomainError> {
+        match (self, other) {
+            (Ok(value), Ok(other)) => Ok((value, other)),
+            (Err(error), Ok(_)) | (Ok(_), Err(error)) => Err(error),
+            (Err(error), Err(other)) => Err(error.merge(other)),
+        }
+    }
+
+    fn and_also<U>(self, other: Result<U, DomainError>) -> Result<U, DomainError> {
+        self.zip(other).map(|(_, other)| other)
+    }
+}
+
+/// The ValidationBuilder runs several checks and collects the ErrorDetails of every failed one
+///
+/// ```
+/// use kern::building_blocks::error::domain_error::DomainError;
+/// use kern::building_blocks::error::error_detail::ErrorDetail;
+/// use kern::building_blocks::error::validation::ValidationBuilder;
+///
+/// struct Tenant {
+///     name: String,
+///     seats: u32,
+/// }
+///
+/// fn seats(seats: i64) -> Result<u32, DomainError> {
+///     u32::try_from(seats).map_err(|_| {
+///         ErrorDetail::new("error.tenant.invalid-seats", "The seats are out of range").into()
+///     })
+/// }
+///
+/// fn tenant(name: &str, requested_seats: i64) -> Result<Tenant, DomainError> {
+///     let mut validation = ValidationBuilder::new();
+///     validation.ensure(
+///         !name.is_empty(),
+///         ErrorDetail::new("error.tenant.invalid-name", "The name is empty"),
+///     );
+///     let seats = validation.check(seats(requested_seats));
+///     validation.build(|| Tenant {
+///         name: name.to_string(),
+///         seats: seats.unwrap(),
+///     })
+/// }
+///
+/// assert_eq!(tenant("acme", 5).unwrap().seats, 5);
+/// assert_eq!(tenant("", -1).err().unwrap().error_details().count(), 2);
+/// ```
+#[derive(Debug, Default)]
+pub struct ValidationBuilder {
+    /// The ErrorDetails of the failed checks
+    error_details: ErrorDetails,
+}
+
+impl ValidationBuilder {
+    /// Creates a ValidationBuilder without errors
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the ErrorDetail if the condition does not hold
+    /// # Arguments
+    /// * `condition` - The condition that must hold
+    /// * `error_detail` - The ErrorDetail describing the violation of the condition
+    pub fn ensure(&mut self, condition: bool, error_detail: ErrorDetail) -> &mut Self {
+        if !condition {
+            self.error_details.insert(error_detail);
+        }
+        self
+    }
+
+    /// Records the ErrorDetail of a failed check
+    /// # Arguments
+    /// * `error_detail` - The ErrorDetail describing the failure
+    pub fn push(&mut self, error_detail: ErrorDetail) -> &mut Self {
+        self.error_details.insert(error_detail);
+        self
+    }
+
+    /// Records the errors of the result and returns its value, if it is successful
+    /// # Arguments
+    /// * `result` - The result of a validation
+    pub fn check<T>(&mut self, result: Result<T, DomainError>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.error_details.extend(error.into_error_details());
+                None
+            }
+        }
+    }
+
+    /// Returns true if any check failed
+    pub fn has_errors(&self) -> bool {
+        !self.error_details.is_empty()
+    }
+
+    /// Returns the errors of the failed checks, if there are any
+    pub fn finish(self) -> Result<(), DomainError> {
+        self.build(|| ())
+    }
+
+    /// Builds the value if every check succeeded, otherwise returns the errors of the failed
+    /// checks
+    /// # Arguments
+    /// * `build` - Builds the value, it is only called if every check succeeded
+    pub fn build<T, F>(self, build: F) -> Result<T, DomainError>
+    where
+        F: FnOnce() -> T,
+    {
+        if self.error_details.is_empty() {
+            Ok(build())
+        } else {
+            Err(self.error_details.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn invalid(key: &'static str) -> DomainError {
+        ErrorDetail::new_const(key, "Invalid").into()
+    }
+
+    #[test]
+    fn given_failed_checks_when_building_then_every_error_is_returned() {
+        let mut validation = ValidationBuilder::new();
+        validation
+            .ensure(
+                true,
+                ErrorDetail::new_const("error.tenant.invalid-name", ""),
+            )
+            .ensure(
+                false,
+                ErrorDetail::new_const("error.tenant.invalid-plan", ""),
+            );
+        let seats: Option<u32> = validation.check(Err(invalid("error.tenant.invalid-seats")));
+
+        assert!(seats.is_none());
+        assert!(validation.has_errors());
+        let error = validation.finish().unwrap_err();
+        assert!(matches!(error, DomainError::Multiple { .. }));
+        let details = error.into_error_details();
+        assert!(details.contains("error.tenant.invalid-plan"));
+        assert!(details.contains("error.tenant.invalid-seats"));
+    }
+
+    #[test]
+    fn given_a_single_failed_check_when_building_then_a_single_error_is_returned() {
+        let mut validation = ValidationBuilder::new();
+        validation.ensure(
+            false,
+            ErrorDetail::new_const("error.tenant.invalid-name", ""),
+        );
+
+        assert!(matches!(
+            validation.build(|| unreachable!()),
+            Err::<(), _>(DomainError::Single { .. })
+        ));
+        assert_eq!(ValidationBuilder::new().build(|| 5), Ok(5));
+    }
+
+    #[test]
+    fn given_failed_results_when_combining_them_then_the_errors_are_merged() {
+        let name: Result<&str, DomainError> = Err(invalid("error.tenant.invalid-name"));
+        let plan: Result<&str, DomainError> = Err(invalid("error.tenant.invalid-plan"));
+        let seats: Result<u32, DomainError> = Ok(5);
+
+        let error = name.zip(seats.clone()).and_also(plan).unwrap_err();
+        assert_eq!(error.error_details().count(), 2);
+
+        let merged =
+            invalid("error.tenant.invalid-name").merge(invalid("error.tenant.invalid-name"));
+        assert!(matches!(merged, DomainError::Single { .. }));
+
+        assert_eq!(Ok::<_, DomainError>("acme").zip(seats), Ok(("acme", 5)));
+    }
+}
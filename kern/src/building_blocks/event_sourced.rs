@@ -0,0 +1,83 @@
+use crate::building_blocks::{
+    aggregate::Aggregate, domain_event::DomainEvent, error::domain_error::DomainError,
+};
+
+/// An EventSourced Aggregate derives its state from the Domain Events it raised instead of
+/// persisting the state itself. Applying every event of its stream in order rehydrates the
+/// Aggregate
+///
+/// ```
+/// use kern::Aggregate;
+/// use kern::DomainEvent;
+/// use kern::building_blocks::aggregate::Aggregate;
+/// use kern::building_blocks::entity::Entity;
+/// use kern::building_blocks::event_sourced::EventSourced;
+/// use kern::building_blocks::ids::EventId;
+/// use chrono::DateTime;
+/// use chrono::Utc;
+/// use uuid::Uuid;
+///
+/// #[derive(kern::Aggregate, Debug)]
+/// pub struct Account {
+///     #[generate_id(Uuid)]
+///     #[entity_id]
+///     id: AccountId,
+///     #[field]
+///     balance: i64,
+///     version: u32
+/// }
+///
+/// #[derive(kern::DomainEvent, Debug)]
+/// pub enum AccountEvent {
+///     Deposited { id: EventId, aggregate_id: AccountId, aggregate_version: u32, occurred_at: DateTime<Utc>, amount: i64 },
+///     Withdrew { id: EventId, aggregate_id: AccountId, aggregate_version: u32, occurred_at: DateTime<Utc>, amount: i64 },
+/// }
+///
+/// impl EventSourced for Account {
+///     type Event = AccountEvent;
+///
+///     fn apply(&mut self, event: &AccountEvent) {
+///         match event {
+///             AccountEvent::Deposited { amount, .. } => self.balance += amount,
+///             AccountEvent::Withdrew { amount, .. } => self.balance -= amount,
+///         }
+///     }
+/// }
+///
+/// let id = AccountId::new(Uuid::new_v4());
+/// let events = vec![
+///     AccountEvent::Deposited { id: EventId::new_random_v4(), aggregate_id: id, aggregate_version: 1, occurred_at: Utc::now(), amount: 100 },
+///     AccountEvent::Withdrew { id: EventId::new_random_v4(), aggregate_id: id, aggregate_version: 2, occurred_at: Utc::now(), amount: 30 },
+/// ];
+///
+/// let account = Account { id, balance: 0, version: 0 }.rehydrate(&events).unwrap();
+///
+/// assert_eq!(*account.balance(), 70);
+/// assert_eq!(account.version(), 2);
+/// ```
+pub trait EventSourced: Aggregate + Sized {
+    /// The Domain Event type the Aggregate raises
+    type Event: DomainEvent;
+
+    /// Mutates the state of the Aggregate according to the Domain Event. Must not fail, the event
+    /// already happened
+    /// # Arguments
+    /// * `event` - The Domain Event to apply
+    fn apply(&mut self, event: &Self::Event);
+
+    /// Rehydrates the Aggregate by applying the Domain Events in order. The version of the
+    /// Aggregate is incremented once per applied event, which fails when the version overflows
+    /// # Arguments
+    /// * `events` - The Domain Events of the Aggregate's stream
+    fn rehydrate<'a, I>(mut self, events: I) -> Result<Self, DomainError>
+    where
+        I: IntoIterator<Item = &'a Self::Event>,
+        Self::Event: 'a,
+    {
+        for event in events {
+            self.apply(event);
+            self.increment_version()?;
+        }
+        Ok(self)
+    }
+}
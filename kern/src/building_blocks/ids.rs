@@ -1,4 +1,5 @@
 use crate::building_blocks::value_object::ValueObject;
+use serde::{Deserialize, Serialize};
 use std::hash::Hash;
 use uuid::Uuid;
 
@@ -6,7 +7,8 @@ use uuid::Uuid;
 pub trait AggregateId: Copy + Clone + Eq + PartialEq + Hash {}
 
 /// The unique identifier of the Event
-#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
+#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct EventId(Uuid);
 
 impl EventId {
@@ -0,0 +1,227 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize, de::IgnoredAny};
+
+use crate::building_blocks::domain_event::{DomainEvent, DynDomainEvent};
+
+/// The PendingEvents are the Domain Events an Aggregate raised that were not published yet.
+///
+/// The PendingEvents belong to the Aggregate instance that raised them, they are never stored with
+/// the Aggregate. Cloning them returns empty PendingEvents, so a Repository that stores a clone of
+/// the Aggregate does not hand the Domain Events out again when it is loaded. They are serialized as
+/// `null` and deserialized as empty PendingEvents, also when the field is missing, so an Aggregate
+/// that derives `Serialize` and `Deserialize` can be stored as JSON, e.g. by the SqliteRepository
+#[derive(Default)]
+pub struct PendingEvents(Vec<Arc<dyn DynDomainEvent>>);
+
+impl PendingEvents {
+    /// Creates empty PendingEvents
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Adds the Domain Event to the PendingEvents
+    /// # Arguments
+    /// * `event` - The Domain Event to add
+    pub fn push(&mut self, event: Arc<dyn DynDomainEvent>) {
+        self.0.push(event)
+    }
+
+    /// Removes and returns every Domain Event in the order they were added
+    pub fn take(&mut self) -> Vec<Arc<dyn DynDomainEvent>> {
+        std::mem::take(&mut self.0)
+    }
+
+    /// The Domain Events in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<dyn DynDomainEvent>> {
+        self.0.iter()
+    }
+
+    /// The number of Domain Events
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if there are no Domain Events
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Clone for PendingEvents {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl Serialize for PendingEvents {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_unit()
+    }
+}
+
+impl<'de> Deserialize<'de> for PendingEvents {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // An Option also accepts a missing field
+        Option::<IgnoredAny>::deserialize(deserializer)?;
+        Ok(Self::new())
+    }
+}
+
+impl std::fmt::Debug for PendingEvents {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|event| event.id()))
+            .finish()
+    }
+}
+
+/// An Aggregate that RecordsEvents keeps the Domain Events it raised during a command, so the
+/// application layer can publish them after the Aggregate was saved.
+///
+/// Add the `pending_events` attribute to a `PendingEvents` field to derive it with
+/// `#[derive(Aggregate)]`
+///
+/// ```
+/// use kern::Aggregate;
+/// use kern::DomainEvent;
+/// use kern::building_blocks::aggregate::Aggregate;
+/// use kern::building_blocks::entity::Entity;
+/// use kern::building_blocks::ids::EventId;
+/// use kern::building_blocks::records_events::{PendingEvents, RecordsEvents};
+/// use chrono::DateTime;
+/// use chrono::Utc;
+/// use uuid::Uuid;
+///
+/// #[derive(kern::Aggregate, Debug)]
+/// pub struct Account {
+///     #[generate_id(Uuid)]
+///     #[entity_id]
+///     id: AccountId,
+///     balance: i64,
+///     version: u32,
+///     #[pending_events]
+///     events: PendingEvents,
+/// }
+///
+/// #[derive(kern::DomainEvent, Debug)]
+/// pub struct Deposited {
+///     id: EventId,
+///     aggregate_id: AccountId,
+///     aggregate_version: u32,
+///     occurred_at: DateTime<Utc>,
+///     amount: i64,
+/// }
+///
+/// impl Account {
+///     pub fn deposit(&mut self, amount: i64) {
+///         self.balance += amount;
+///         self.record(Deposited {
+///             id: EventId::new_random_v4(),
+///             aggregate_id: self.id,
+///             aggregate_version: self.version + 1,
+///             occurred_at: Utc::now(),
+///             amount,
+///         });
+///     }
+/// }
+///
+/// let mut account = Account {
+///     id: AccountId::new(Uuid::new_v4()),
+///     balance: 0,
+///     version: 0,
+///     events: PendingEvents::new(),
+/// };
+/// account.deposit(10);
+///
+/// let events = account.take_events();
+/// assert_eq!(events.len(), 1);
+/// assert!(events[0].as_any().is::<Deposited>());
+/// assert!(account.take_events().is_empty());
+/// ```
+pub trait RecordsEvents {
+    /// The Domain Events the Aggregate raised that were not taken yet
+    fn pending_events(&self) -> &PendingEvents;
+
+    /// The mutable Domain Events the Aggregate raised that were not taken yet
+    fn pending_events_mut(&mut self) -> &mut PendingEvents;
+
+    /// Records the Domain Event so it can be published after the Aggregate was saved
+    /// # Arguments
+    /// * `event` - The Domain Event the Aggregate raised
+    fn record<E>(&mut self, event: E)
+    where
+        E: DomainEvent + Send + Sync + 'static,
+        Self: Sized,
+    {
+        self.pending_events_mut().push(Arc::new(event))
+    }
+
+    /// Removes and returns the recorded Domain Events in the order they were recorded
+    fn take_events(&mut self) -> Vec<Arc<dyn DynDomainEvent>> {
+        self.pending_events_mut().take()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::building_blocks::ids::{AggregateId, EventId};
+    use chrono::{DateTime, Utc};
+    use uuid::Uuid;
+
+    #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+    struct AccountId(Uuid);
+
+    impl AggregateId for AccountId {}
+
+    #[derive(crate::DomainEvent, Debug)]
+    struct Opened {
+        id: EventId,
+        aggregate_id: AccountId,
+        aggregate_version: u32,
+        occurred_at: DateTime<Utc>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Account {
+        balance: i64,
+        events: PendingEvents,
+    }
+
+    fn account() -> Account {
+        let mut events = PendingEvents::new();
+        events.push(Arc::new(Opened {
+            id: EventId::new_random_v4(),
+            aggregate_id: AccountId(Uuid::new_v4()),
+            aggregate_version: 1,
+            occurred_at: Utc::now(),
+        }));
+        Account { balance: 5, events }
+    }
+
+    #[test]
+    fn given_pending_events_when_cloning_then_the_clone_is_empty() {
+        let account = account();
+
+        assert_eq!(account.events.len(), 1);
+        assert!(account.events.clone().is_empty());
+    }
+
+    #[test]
+    fn given_pending_events_when_serializing_then_they_are_skipped() {
+        let json = serde_json::to_value(account()).unwrap();
+        assert_eq!(json, serde_json::json!({ "balance": 5, "events": null }));
+
+        let account: Account = serde_json::from_value(json).unwrap();
+        assert!(account.events.is_empty());
+        let account: Account = serde_json::from_str(r#"{ "balance": 5 }"#).unwrap();
+        assert!(account.events.is_empty());
+    }
+}
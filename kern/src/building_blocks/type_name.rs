@@ -0,0 +1,35 @@
+/// A TypeName names a type of the domain. The `Aggregate`, `Entity`, `ValueObject`, `Request` and
+/// `AuthenticatedRequest` derives implement it with the snake case name of the type and the kebab
+/// case name of the type as the prefix of its error keys, which the `#[error_prefix = "prefix"]`
+/// attribute overrides
+///
+/// ```
+/// use kern::building_blocks::type_name::TypeName;
+///
+/// #[derive(kern::ValueObject, Debug)]
+/// #[error_prefix = "billing-address"]
+/// pub struct PostalAddress {
+///     zip_code: String,
+/// }
+///
+/// assert_eq!(PostalAddress::type_name(), "postal_address");
+/// assert_eq!(PostalAddress::error_prefix(), "billing-address");
+///
+/// #[derive(kern::ValueObject, Debug)]
+/// pub struct PurchaseOrderNumber {
+///     value: u32,
+/// }
+///
+/// assert_eq!(PurchaseOrderNumber::type_name(), "purchase_order_number");
+/// assert_eq!(PurchaseOrderNumber::error_prefix(), "purchase-order-number");
+/// ```
+pub trait TypeName {
+    /// The snake case name of the type
+    fn type_name() -> &'static str;
+
+    /// The type segment of the error keys of the type, `error.<prefix>.<slug>`, in lowercase kebab
+    /// case. Defaults to the type name
+    fn error_prefix() -> &'static str {
+        Self::type_name()
+    }
+}
@@ -70,4 +70,242 @@ use std::hash::Hash;
 /// assert_ne!(a, b);
 ///
 /// ```
+///
+/// The `vo` attribute generates a `try_new` constructor that enforces the invariants of the value
+/// object
+///
+/// ```
+/// use kern::building_blocks::error::domain_error::DomainError;
+///
+/// #[derive(kern::ValueObject, Debug)]
+/// pub struct Discount {
+///     #[vo(non_empty, max_len = 16)]
+///     code: String,
+///     #[vo(range(0..=100))]
+///     percentage: u8,
+/// }
+///
+/// assert!(Discount::try_new("SUMMER".to_string(), 20).is_ok());
+///
+/// let error = Discount::try_new(String::new(), 120).unwrap_err();
+/// let details = error.into_error_details();
+/// assert!(details.contains("error.discount.invalid-code"));
+/// assert!(details.contains("error.discount.invalid-percentage"));
+/// ```
+///
+/// The NewType derive generates the boilerplate of a value object that wraps a single value
+///
+/// ```
+/// #[derive(kern::NewType, Clone, PartialEq, Eq, Hash, Debug)]
+/// #[vo(non_empty, max_len = 3)]
+/// pub struct CurrencyCode(String);
+///
+/// let code: CurrencyCode = "EUR".parse().unwrap();
+/// assert_eq!(code.value(), "EUR");
+/// assert_eq!(code.to_string(), "EUR");
+/// assert_eq!(serde_json::to_string(&code).unwrap(), "\"EUR\"");
+/// assert!(CurrencyCode::new("EURO".to_string()).is_err());
+/// assert!(serde_json::from_str::<CurrencyCode>("\"\"").is_err());
+/// ```
 pub trait ValueObject: Eq + PartialEq + Hash + Clone {}
+
+/// The Length of a value is what the `non_empty`, `min_len` and `max_len` checks of the
+/// `#[vo(...)]` attribute of the ValueObject derive compare, i.e. the number of characters of a
+/// string and the number of elements of a collection
+pub trait Length {
+    /// The length of the value
+    fn length(&self) -> usize;
+}
+
+impl Length for str {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl Length for String {
+    fn length(&self) -> usize {
+        self.as_str().length()
+    }
+}
+
+impl<T> Length for [T] {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for std::collections::HashSet<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for std::collections::BTreeSet<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::building_blocks::error::{domain_error::DomainError, error_detail::ErrorDetail};
+
+    fn no_spaces(value: &str) -> Result<(), DomainError> {
+        if value.contains(' ') {
+            return Err(ErrorDetail::new("error.email-address.contains-spaces", "Spaces").into());
+        }
+        Ok(())
+    }
+
+    fn same_domain(address: &EmailAddress) -> Result<(), DomainError> {
+        if address.domain != "example.com" {
+            return Err(ErrorDetail::new("error.email-address.foreign-domain", "Foreign").into());
+        }
+        Ok(())
+    }
+
+    #[derive(crate::ValueObject, Debug)]
+    #[error_prefix = "email-address"]
+    #[vo(validate = "same_domain")]
+    struct EmailAddress {
+        #[vo(min_len = 2, validate = "no_spaces")]
+        local_part: String,
+        #[vo(non_empty)]
+        domain: String,
+    }
+
+    #[test]
+    fn given_invalid_fields_when_creating_then_every_failed_check_is_returned() {
+        let error = EmailAddress::try_new("a b".to_string(), String::new()).unwrap_err();
+        let details = error.into_error_details();
+
+        assert_eq!(details.len(), 2);
+        assert!(details.contains("error.email-address.contains-spaces"));
+        let domain = &details.get("error.email-address.invalid-domain")[0];
+        assert_eq!(domain.message(), "The domain must not be empty");
+        assert_eq!(domain.field(), Some("/domain"));
+        assert_eq!(domain.params()["code"], "length");
+
+        let error = EmailAddress::try_new("a".to_string(), "example.com".to_string()).unwrap_err();
+        let detail = error.error_details().next().unwrap();
+        assert_eq!(detail.key(), "error.email-address.invalid-local-part");
+        assert_eq!(detail.params()["min"], 2);
+    }
+
+    fn lowercase(value: &str) -> Result<(), DomainError> {
+        if value.chars().any(char::is_uppercase) {
+            return Err(ErrorDetail::new("error.username.uppercase", "Uppercase").into());
+        }
+        Ok(())
+    }
+
+    #[derive(crate::NewType, Clone, PartialEq, Eq, Hash, Debug)]
+    #[new_type(schema)]
+    struct Username(#[vo(min_len = 3, validate = "lowercase")] String);
+
+    #[derive(crate::NewType, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    #[error_prefix = "seat-count"]
+    #[vo(range(1..=100))]
+    struct Seats {
+        seats: u32,
+    }
+
+    #[test]
+    fn given_a_new_type_with_checks_when_creating_then_every_failed_check_is_returned() {
+        let username = Username::new("alice".to_string()).unwrap();
+        assert_eq!(username.as_ref(), "alice");
+        assert_eq!(username.clone().into_inner(), "alice");
+        assert_eq!(Username::try_from("alice".to_string()), Ok(username));
+
+        let details = Username::new("Al".to_string())
+            .unwrap_err()
+            .into_error_details();
+        assert_eq!(details.len(), 2);
+        assert!(details.contains("error.username.uppercase"));
+        let detail = &details.get("error.username.invalid")[0];
+        assert_eq!(
+            detail.message(),
+            "The length of the username must be at least 3"
+        );
+        assert_eq!(detail.field(), None);
+        assert_eq!(detail.params()["min"], 3);
+    }
+
+    #[derive(crate::NewType, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    #[vo(range(0..5))]
+    struct Rating(u8);
+
+    #[test]
+    fn given_a_range_check_when_failing_then_only_an_inclusive_end_is_the_max() {
+        let seats = Seats::new(0).unwrap_err().into_error_details();
+        let seats = &seats.get("error.seat-count.invalid")[0];
+        assert_eq!(seats.params()["min"], 1);
+        assert_eq!(seats.params()["max"], 100);
+
+        let rating = Rating::new(5).unwrap_err().into_error_details();
+        let rating = &rating.get("error.rating.invalid")[0];
+        assert_eq!(rating.params()["min"], 0);
+        assert_eq!(rating.params()["exclusive_max"], 5);
+        assert!(!rating.params().contains_key("max"));
+    }
+
+    #[test]
+    fn given_a_new_type_when_parsing_and_deserializing_then_the_checks_run() {
+        assert_eq!("42".parse::<Seats>().map(|seats| *seats.value()), Ok(42));
+        assert_eq!(Seats::new(42).unwrap().to_string(), "42");
+
+        let error = "0".parse::<Seats>().unwrap_err();
+        assert!(matches!(
+            error,
+            DomainError::Single { error_detail }
+                if error_detail.key() == "error.seat-count.invalid"
+                    && error_detail.params()["code"] == "range"
+        ));
+        let error = "many".parse::<Seats>().unwrap_err();
+        assert!(matches!(
+            error,
+            DomainError::Single { error_detail }
+                if error_detail.params()["code"] == "format"
+        ));
+
+        let seats: Seats = serde_json::from_str("7").unwrap();
+        assert_eq!(serde_json::to_value(seats).unwrap(), serde_json::json!(7));
+        assert!(serde_json::from_str::<Seats>("101").is_err());
+    }
+
+    #[test]
+    fn given_a_new_type_with_a_schema_when_documenting_then_the_schema_of_the_value_is_used() {
+        use utoipa::{PartialSchema, ToSchema};
+
+        assert!(Username::schema() == String::schema());
+        assert_eq!(Username::name(), "Username");
+    }
+
+    #[test]
+    fn given_invalid_fields_when_creating_then_the_type_validation_does_not_run() {
+        let details = EmailAddress::try_new("a".to_string(), "example.org".to_string())
+            .unwrap_err()
+            .into_error_details();
+
+        assert_eq!(details.len(), 1);
+        assert!(details.contains("error.email-address.invalid-local-part"));
+        assert!(!details.contains("error.email-address.foreign-domain"));
+    }
+
+    #[test]
+    fn given_valid_fields_when_creating_then_the_type_validation_runs() {
+        assert!(EmailAddress::try_new("ab".to_string(), "example.com".to_string()).is_ok());
+        assert!(matches!(
+            EmailAddress::try_new("ab".to_string(), "example.org".to_string()),
+            Err(DomainError::Single { error_detail })
+                if error_detail.key() == "error.email-address.foreign-domain"
+        ));
+    }
+}
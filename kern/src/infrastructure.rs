@@ -1,2 +1,6 @@
 pub mod error;
 pub mod event;
+pub mod http;
+#[cfg(feature = "localization")]
+pub mod localization;
+pub mod persistence;
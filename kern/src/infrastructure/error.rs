@@ -1,2 +1,4 @@
 #[cfg(feature = "axum")]
 pub mod axum_extensions;
+#[cfg(feature = "axum")]
+pub mod problem_details;
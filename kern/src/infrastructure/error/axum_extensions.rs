@@ -1,8 +1,18 @@
+use std::collections::BTreeMap;
+
 use crate::{
-    application::error::forbidden_error::ForbiddenError,
-    building_blocks::error::domain_error::DomainError,
+    application::error::{
+        application_error::ApplicationError, conflict_error::ConflictError,
+        forbidden_error::ForbiddenError, not_found_error::NotFoundError,
+        unauthorized_error::UnauthorizedError, unavailable_error::UnavailableError,
+    },
+    building_blocks::error::{domain_error::DomainError, error_detail::ErrorDetail},
+};
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, header, response::Parts},
+    response::{IntoResponse, Response},
 };
-use axum::{Json, http::StatusCode, response::IntoResponse};
 
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -10,6 +20,12 @@ use axum::{Json, http::StatusCode, response::IntoResponse};
 pub struct StatusCodeError {
     pub error_key: String,
     pub description: String,
+    /// The JSON pointer to the offending field, e.g. `/address/zip`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    /// The named parameters of the error, e.g. `min`, `max` or `value`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, serde_json::Value>,
 }
 
 impl StatusCodeError {
@@ -17,9 +33,19 @@ impl StatusCodeError {
         Self {
             error_key,
             description,
+            field: None,
+            params: BTreeMap::new(),
         }
     }
 
+    pub fn field(&self) -> Option<&String> {
+        self.field.as_ref()
+    }
+
+    pub fn params(&self) -> &BTreeMap<String, serde_json::Value> {
+        &self.params
+    }
+
     pub fn error_key(&self) -> &String {
         &self.error_key
     }
@@ -36,50 +62,244 @@ pub struct StatusCodeErrors {
     pub errors: Vec<StatusCodeError>,
 }
 
+impl From<&ErrorDetail> for StatusCodeError {
+    fn from(value: &ErrorDetail) -> Self {
+        Self {
+            error_key: value.key().to_string(),
+            description: value.message().to_string(),
+            field: value.field().map(str::to_string),
+            params: value
+                .params()
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+        }
+    }
+}
+
 impl StatusCodeErrors {
     pub fn new(errors: Vec<StatusCodeError>) -> Self {
         Self { errors }
     }
 }
 
+/// The ErrorDetails an error response was rendered from, so a middleware such as
+/// `render_errors` can render the response again
+#[derive(Clone, Debug)]
+pub(crate) struct ResponseErrorDetails {
+    /// The ErrorDetails of the response
+    pub(crate) details: Vec<ErrorDetail>,
+    /// Whether the ErrorDetails are rendered as StatusCodeErrors instead of a StatusCodeError
+    pub(crate) multiple: bool,
+}
+
+impl ResponseErrorDetails {
+    /// Creates the ResponseErrorDetails of an error with a single ErrorDetail
+    /// # Arguments
+    /// * `detail` - The ErrorDetail of the error
+    pub(crate) fn single(detail: ErrorDetail) -> Self {
+        Self {
+            details: vec![detail],
+            multiple: false,
+        }
+    }
+
+    /// Renders the ErrorDetails as StatusCodeError or StatusCodeErrors and attaches them to the
+    /// response
+    /// # Arguments
+    /// * `status` - The status code of the response
+    pub(crate) fn render(self, status: StatusCode) -> Response {
+        let body = match self.details.as_slice() {
+            [detail] if !self.multiple => Json(StatusCodeError::from(detail)).into_response(),
+            details => Json(StatusCodeErrors::new(
+                details.iter().map(StatusCodeError::from).collect(),
+            ))
+            .into_response(),
+        };
+        let mut response = (status, body).into_response();
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+/// Replaces the body of an error response, keeping its status and headers
+/// # Arguments
+/// * `parts` - The parts of the error response
+/// * `body` - The response whose body, content type and extensions are used
+pub(crate) fn with_body(mut parts: Parts, body: Response) -> Response {
+    let (body_parts, body) = body.into_parts();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    if let Some(content_type) = body_parts.headers.get(header::CONTENT_TYPE) {
+        parts
+            .headers
+            .insert(header::CONTENT_TYPE, content_type.clone());
+    }
+    parts.extensions.extend(body_parts.extensions);
+    Response::from_parts(parts, body)
+}
+
 impl IntoResponse for DomainError {
-    fn into_response(self) -> axum::response::Response {
-        match self {
-            Self::Single { error_detail } => (
-                StatusCode::UNPROCESSABLE_ENTITY,
-                Json(StatusCodeError::new(
-                    error_detail.key().to_string(),
-                    error_detail.message().to_string(),
-                )),
-            )
-                .into_response(),
-            Self::Multiple { error_details } => {
-                let errors: Vec<StatusCodeError> = error_details
-                    .into_iter()
-                    .map(|detail| {
-                        StatusCodeError::new(detail.key().to_string(), detail.message().to_string())
-                    })
-                    .collect();
-                (
-                    StatusCode::UNPROCESSABLE_ENTITY,
-                    Json(StatusCodeErrors::new(errors)),
-                )
-                    .into_response()
-            }
+    fn into_response(self) -> Response {
+        ResponseErrorDetails {
+            details: self.error_details().cloned().collect(),
+            multiple: matches!(self, Self::Multiple { .. }),
         }
+        .render(StatusCode::UNPROCESSABLE_ENTITY)
     }
 }
 
 impl From<ForbiddenError> for StatusCodeError {
     fn from(value: ForbiddenError) -> Self {
-        let detail = value.error_detail();
-        StatusCodeError::new(detail.key().to_string(), detail.message().to_string())
+        value.error_detail().into()
     }
 }
 
 impl IntoResponse for ForbiddenError {
-    fn into_response(self) -> axum::response::Response {
-        let error_response: StatusCodeError = self.into();
-        (StatusCode::FORBIDDEN, Json(error_response)).into_response()
+    fn into_response(self) -> Response {
+        ResponseErrorDetails::single(self.error_detail().clone()).render(StatusCode::FORBIDDEN)
+    }
+}
+
+impl From<UnauthorizedError> for StatusCodeError {
+    fn from(value: UnauthorizedError) -> Self {
+        value.error_detail().into()
+    }
+}
+
+impl IntoResponse for UnauthorizedError {
+    fn into_response(self) -> Response {
+        let mut response = ResponseErrorDetails::single(self.error_detail().clone())
+            .render(StatusCode::UNAUTHORIZED);
+        response
+            .headers_mut()
+            .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        response
+    }
+}
+
+impl From<NotFoundError> for StatusCodeError {
+    fn from(value: NotFoundError) -> Self {
+        value.error_detail().into()
+    }
+}
+
+impl IntoResponse for NotFoundError {
+    fn into_response(self) -> Response {
+        ResponseErrorDetails::single(self.error_detail().clone()).render(StatusCode::NOT_FOUND)
+    }
+}
+
+impl From<ConflictError> for StatusCodeError {
+    fn from(value: ConflictError) -> Self {
+        value.error_detail().into()
+    }
+}
+
+impl IntoResponse for ConflictError {
+    fn into_response(self) -> Response {
+        ResponseErrorDetails::single(self.error_detail().clone()).render(StatusCode::CONFLICT)
+    }
+}
+
+impl From<UnavailableError> for StatusCodeError {
+    fn from(value: UnavailableError) -> Self {
+        value.error_detail().into()
+    }
+}
+
+impl IntoResponse for UnavailableError {
+    fn into_response(self) -> Response {
+        ResponseErrorDetails::single(self.error_detail().clone())
+            .render(StatusCode::SERVICE_UNAVAILABLE)
+    }
+}
+
+impl ApplicationError {
+    /// The HTTP status code the ApplicationError is answered with
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Domain(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Self::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
+
+impl IntoResponse for ApplicationError {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Domain(error) => error.into_response(),
+            Self::NotFound(error) => error.into_response(),
+            Self::Conflict(error) => error.into_response(),
+            Self::Forbidden(error) => error.into_response(),
+            Self::Unauthorized(error) => error.into_response(),
+            Self::Unavailable(error) => error.into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::application::error::{
+        concurrency_conflict::ConcurrencyConflict, repository_error::RepositoryError,
+    };
+
+    #[test]
+    fn given_an_error_detail_with_a_field_when_serializing_then_field_and_params_are_included() {
+        let detail = ErrorDetail::new("error.address.invalid-zip", "Invalid zip code")
+            .with_field("/address/zip")
+            .with_param("max", 5);
+
+        assert_eq!(
+            serde_json::to_value(StatusCodeError::from(&detail)).unwrap(),
+            serde_json::json!({
+                "errorKey": "error.address.invalid-zip",
+                "description": "Invalid zip code",
+                "field": "/address/zip",
+                "params": { "max": 5 },
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(StatusCodeError::from(&ErrorDetail::new("error.a", "A"))).unwrap(),
+            serde_json::json!({ "errorKey": "error.a", "description": "A" })
+        );
+    }
+
+    #[test]
+    fn given_application_errors_when_responding_then_each_maps_to_its_status_code() {
+        let errors: Vec<ApplicationError> = vec![
+            DomainError::single(ErrorDetail::new("error.tenant.invalid-name", "Invalid")).into(),
+            NotFoundError::new(ErrorDetail::new("error.tenant.not-found", "Not found")).into(),
+            ConcurrencyConflict::new("tenant", 1, 2).into(),
+            ForbiddenError::for_use_case("delete-tenant").into(),
+            UnauthorizedError::new(ErrorDetail::new("error.authentication.missing-token", ""))
+                .into(),
+            RepositoryError::storage("disk full").into(),
+        ];
+
+        let statuses: Vec<StatusCode> = errors
+            .into_iter()
+            .map(|error| {
+                let status = error.status_code();
+                assert_eq!(error.into_response().status(), status);
+                status
+            })
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                StatusCode::UNPROCESSABLE_ENTITY,
+                StatusCode::NOT_FOUND,
+                StatusCode::CONFLICT,
+                StatusCode::FORBIDDEN,
+                StatusCode::UNAUTHORIZED,
+                StatusCode::SERVICE_UNAVAILABLE,
+            ]
+        );
     }
 }
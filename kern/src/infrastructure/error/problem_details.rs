@@ -0,0 +1,322 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    Json,
+    extract::{Request, State},
+    http::{HeaderValue, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    application::ids::RequestId,
+    building_blocks::error::error_detail::ErrorDetail,
+    infrastructure::{
+        error::axum_extensions::{ResponseErrorDetails, with_body},
+        http::request_context::REQUEST_ID_HEADER,
+    },
+};
+
+/// The media type of problem details
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// A ProblemDetails is the RFC 9457 representation of an error response. The `type` is the key
+/// and the `title` the message of the ErrorDetail, its field and params are the `field` and
+/// `params` extension members; several ErrorDetails are listed in the `errors` extension member
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProblemDetails {
+    /// The key of the error, or `about:blank` if the response has several errors
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// The message of the error, or the reason phrase of the status if the response has several
+    /// errors
+    pub title: String,
+    /// The HTTP status code
+    pub status: u16,
+    /// The identifier of the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// The JSON pointer to the offending field of the error, if the response has a single error
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    /// The named parameters of the error, if the response has a single error
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, serde_json::Value>,
+    /// The errors of the response, if it has several
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<Problem>>,
+}
+
+/// A Problem is one error of the `errors` extension member of a ProblemDetails
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Problem {
+    /// The key of the error
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// The message of the error
+    pub title: String,
+    /// The JSON pointer to the offending field, e.g. `/address/zip`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    /// The named parameters of the error, e.g. `min`, `max` or `value`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, serde_json::Value>,
+}
+
+/// The ResponseFormat selects how the error responses of the application are rendered
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ResponseFormat {
+    /// `StatusCodeError` and `StatusCodeErrors`, unless the client accepts problem details
+    #[default]
+    StatusCode,
+    /// RFC 9457 problem details
+    ProblemDetails,
+}
+
+/// Returns true if the client asked for problem details in its `Accept` header
+/// # Arguments
+/// * `request` - The HTTP request
+fn accepts_problem_details(request: &Request) -> bool {
+    request
+        .headers()
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media_type| {
+            media_type
+                .split(';')
+                .next()
+                .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case(PROBLEM_JSON))
+        })
+}
+
+/// Renders the error responses of the application as problem details when the ResponseFormat is
+/// ProblemDetails or the client accepts `application/problem+json`.
+///
+/// The middleware also makes sure every request has an `X-Request-Id` header, generating a
+/// time-ordered one if it is missing, so the `instance` of the problem details is the RequestId
+/// the RequestContext extractor reads
+///
+/// ```
+/// use axum::{Router, middleware, routing::get};
+/// use kern::application::error::forbidden_error::ForbiddenError;
+/// use kern::infrastructure::error::problem_details::{ResponseFormat, render_errors};
+///
+/// async fn delete_tenant() -> Result<(), ForbiddenError> {
+///     Err(ForbiddenError::for_use_case("delete-tenant"))
+/// }
+///
+/// let app: Router = Router::new()
+///     .route("/tenants", get(delete_tenant))
+///     .layer(middleware::from_fn_with_state(
+///         ResponseFormat::ProblemDetails,
+///         render_errors,
+///     ));
+/// ```
+pub async fn render_errors(
+    State(format): State<ResponseFormat>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let request_id = match request.headers().get(REQUEST_ID_HEADER) {
+        Some(request_id) => request_id.clone(),
+        None => {
+            let request_id = HeaderValue::from_str(&RequestId::new_random_v7().value().to_string())
+                .expect("A UUID is a valid header value");
+            request
+                .headers_mut()
+                .insert(REQUEST_ID_HEADER, request_id.clone());
+            request_id
+        }
+    };
+    let problem_details =
+        format == ResponseFormat::ProblemDetails || accepts_problem_details(&request);
+
+    let response = next.run(request).await;
+    let Some(ResponseErrorDetails { details, .. }) = response
+        .extensions()
+        .get::<ResponseErrorDetails>()
+        .filter(|_| problem_details)
+        .cloned()
+    else {
+        return response;
+    };
+
+    let (parts, _) = response.into_parts();
+    let problem = match details.as_slice() {
+        [detail] => ProblemDetails {
+            problem_type: detail.key().to_string(),
+            title: detail.message().to_string(),
+            status: parts.status.as_u16(),
+            instance: request_id.to_str().ok().map(str::to_string),
+            field: detail.field().map(str::to_string),
+            params: params(detail),
+            errors: None,
+        },
+        details => ProblemDetails {
+            problem_type: "about:blank".to_string(),
+            title: parts
+                .status
+                .canonical_reason()
+                .unwrap_or_default()
+                .to_string(),
+            status: parts.status.as_u16(),
+            instance: request_id.to_str().ok().map(str::to_string),
+            field: None,
+            params: BTreeMap::new(),
+            errors: Some(
+                details
+                    .iter()
+                    .map(|detail| Problem {
+                        problem_type: detail.key().to_string(),
+                        title: detail.message().to_string(),
+                        field: detail.field().map(str::to_string),
+                        params: params(detail),
+                    })
+                    .collect(),
+            ),
+        },
+    };
+
+    let mut response = with_body(parts, Json(problem).into_response());
+    response
+        .headers_mut()
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+    response
+}
+
+/// The named parameters of the ErrorDetail
+/// # Arguments
+/// * `detail` - The ErrorDetail
+fn params(detail: &ErrorDetail) -> BTreeMap<String, serde_json::Value> {
+    detail
+        .params()
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use axum::{Router, body::Body, http::StatusCode, middleware, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{
+        application::error::forbidden_error::ForbiddenError,
+        building_blocks::error::domain_error::DomainError,
+    };
+
+    async fn forbidden() -> Result<(), ForbiddenError> {
+        Err(ForbiddenError::for_use_case("delete-tenant"))
+    }
+
+    async fn invalid() -> Result<(), DomainError> {
+        Err(DomainError::multiple(HashSet::from([
+            ErrorDetail::new("error.tenant.invalid-name", "The name is empty"),
+            ErrorDetail::new("error.tenant.invalid-plan", "The plan is unknown"),
+        ])))
+    }
+
+    async fn too_short() -> Result<(), DomainError> {
+        Err(
+            ErrorDetail::new("error.tenant.name.too-short", "The name is too short")
+                .with_field("/name")
+                .with_param("min", 3)
+                .into(),
+        )
+    }
+
+    fn app(format: ResponseFormat) -> Router {
+        Router::new()
+            .route("/forbidden", get(forbidden))
+            .route("/too-short", get(too_short))
+            .route("/invalid", get(invalid))
+            .layer(middleware::from_fn_with_state(format, render_errors))
+    }
+
+    async fn send(app: Router, request: axum::http::request::Builder) -> (Response, Vec<u8>) {
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let (parts, body) = response.into_parts();
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        (Response::from_parts(parts, Body::empty()), bytes.to_vec())
+    }
+
+    #[tokio::test]
+    async fn given_the_problem_details_format_when_failing_then_problem_json_is_returned() {
+        let (response, body) = send(
+            app(ResponseFormat::ProblemDetails),
+            Request::builder()
+                .uri("/forbidden")
+                .header(REQUEST_ID_HEADER, "0190b2c4-8e2d-7c6a-9f1e-1a2b3c4d5e6f"),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+        assert_eq!(
+            serde_json::from_slice::<ProblemDetails>(&body).unwrap(),
+            ProblemDetails {
+                problem_type: "error.delete-tenant.forbidden".to_string(),
+                title: "Not allowed to execute delete-tenant".to_string(),
+                status: 403,
+                instance: Some("0190b2c4-8e2d-7c6a-9f1e-1a2b3c4d5e6f".to_string()),
+                field: None,
+                params: BTreeMap::new(),
+                errors: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn given_a_single_field_error_when_failing_then_its_field_and_params_are_returned() {
+        let (response, body) = send(
+            app(ResponseFormat::ProblemDetails),
+            Request::builder().uri("/too-short"),
+        )
+        .await;
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem.problem_type, "error.tenant.name.too-short");
+        assert_eq!(problem.field.as_deref(), Some("/name"));
+        assert_eq!(problem.params["min"], 3);
+        assert!(problem.errors.is_none());
+    }
+
+    #[tokio::test]
+    async fn given_an_accept_header_when_failing_then_the_format_is_negotiated() {
+        let (response, body) = send(
+            app(ResponseFormat::StatusCode),
+            Request::builder()
+                .uri("/invalid")
+                .header(header::ACCEPT, "application/problem+json; q=1.0"),
+        )
+        .await;
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem.problem_type, "about:blank");
+        assert_eq!(problem.errors.unwrap().len(), 2);
+        assert!(problem.instance.is_some());
+
+        let (response, body) = send(
+            app(ResponseFormat::StatusCode),
+            Request::builder().uri("/forbidden"),
+        )
+        .await;
+
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        assert!(
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()["errorKey"].is_string()
+        );
+    }
+}
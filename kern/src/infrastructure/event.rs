@@ -1,2 +1,16 @@
 #[cfg(feature = "event_bus")]
 pub mod event_bus;
+#[cfg(feature = "file_store")]
+pub mod file_event_store;
+#[cfg(feature = "file_store")]
+pub mod file_outbox;
+#[cfg(feature = "in_memory")]
+pub mod in_memory_event_store;
+#[cfg(feature = "in_memory")]
+pub mod in_memory_outbox;
+#[cfg(feature = "outbox")]
+pub mod outbox_relay;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_event_store;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_outbox;
@@ -4,7 +4,10 @@ use dashmap::DashMap;
 use tokio::sync::{broadcast, watch};
 
 use crate::{
-    application::event::{EventBus, EventPublisher},
+    application::{
+        error::publish_error::PublishError,
+        event::{EventBus, EventPublisher},
+    },
     building_blocks::domain_event::DynDomainEvent,
 };
 
@@ -54,6 +57,17 @@ impl EventPublisher for TokioEventBus {
         let tx = self.get_publisher(topic);
         let _ = tx.send(event);
     }
+
+    fn try_publish(
+        &self,
+        topic: &'static str,
+        event: Arc<dyn DynDomainEvent>,
+    ) -> Result<(), PublishError> {
+        let tx = self.get_publisher(topic);
+        tx.send(event)
+            .map(|_| ())
+            .map_err(|_| PublishError::new(topic, "No handler is registered to the topic"))
+    }
 }
 
 impl EventBus for TokioEventBus {
@@ -91,6 +105,7 @@ mod test {
     use crate::building_blocks::{
         domain_event::{DomainEvent, DynDomainEvent},
         ids::{AggregateId, EventId},
+        records_events::PendingEvents,
     };
     use chrono::{DateTime, Utc};
     use std::sync::{
@@ -202,6 +217,27 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn given_pending_events_when_publishing_all_then_subscriber_receives_them() {
+        let bus = TokioEventBus::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        bus.register_handler(
+            "account-created",
+            Box::new(AccountHandler {
+                count: count.clone(),
+            }),
+        );
+
+        let mut pending = PendingEvents::new();
+        pending.push(Arc::new(CreatedAccount::new(Uuid::new_v4())));
+        pending.push(Arc::new(CreatedAccount::new(Uuid::new_v4())));
+        bus.publish_all("account-created", pending.take());
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+        assert!(pending.is_empty());
+    }
+
     #[tokio::test]
     async fn test_shutdown_stops_listeners() {
         let bus = TokioEventBus::new();
@@ -0,0 +1,821 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    application::{
+        error::{concurrency_conflict::ConcurrencyConflict, event_store_error::EventStoreError},
+        event_store::{EventStore, RecordedEvent, StreamId, validate_append},
+    },
+    building_blocks::{domain_event::DomainEvent, event_sourced::EventSourced, ids::EventId},
+};
+
+/// The default size after which a new segment file is started
+pub const DEFAULT_MAX_SEGMENT_BYTES: u64 = 64 * 1024 * 1024;
+
+/// The length of a record header: the payload length and the payload checksum
+const RECORD_HEADER_BYTES: usize = 8;
+
+/// The file extension of a segment file
+const SEGMENT_EXTENSION: &str = "segment";
+
+/// The FileEventStore is an EventStore that persists the Domain Events in append-only segment files
+/// inside a directory, so they survive a process restart without a database.
+///
+/// Every append is written as a single record: a little-endian `u32` payload length, a CRC-32 of
+/// the payload and the JSON encoded Domain Events. The record is fsynced before the append returns.
+/// A new segment file is started once the active one exceeds its maximum size.
+///
+/// The segment files are the source of truth. A record that runs past the end of the last segment
+/// is the result of a torn write and is truncated when the FileEventStore is opened, which discards
+/// the whole append it belonged to. Any other record whose checksum does not match is corrupt, and
+/// opening fails rather than discarding the records after it.
+///
+/// The index of positions per AggregateId is not persisted. Opening the FileEventStore reads and
+/// deserializes every Domain Event of every segment to rebuild it, and the index as well as the
+/// identifiers of every Domain Event are kept in memory. Opening takes time proportional to the
+/// size of the store and memory proportional to its number of Domain Events, so the
+/// FileEventStore suits stores that fit comfortably in memory.
+///
+/// The file I/O of the EventStore methods runs on the blocking thread pool of tokio, so it does
+/// not stall the async executor. Appends are serialized by a lock
+pub struct FileEventStore<A>
+where
+    A: EventSourced,
+{
+    shared: Arc<Shared<A>>,
+}
+
+/// The state of a FileEventStore, which is shared with the blocking tasks that do its file I/O
+struct Shared<A>
+where
+    A: EventSourced,
+{
+    directory: PathBuf,
+    max_segment_bytes: u64,
+    inner: Mutex<Inner<A::Event>>,
+}
+
+struct Inner<E>
+where
+    E: DomainEvent,
+{
+    /// The segment that records are appended to
+    active: ActiveSegment,
+    /// The location of every Domain Event in the order it was appended
+    log: Vec<Location>,
+    /// The positions in the log of each stream's Domain Events
+    streams: HashMap<E::Id, Vec<usize>>,
+    /// The identifiers of every appended Domain Event
+    event_ids: HashSet<EventId>,
+    /// True once a failed append could not be rolled back. The end of the active segment is
+    /// unknown then, so every further append is refused until the FileEventStore is reopened
+    failed: bool,
+}
+
+struct ActiveSegment {
+    number: u64,
+    file: File,
+    len: u64,
+}
+
+/// The location of a Domain Event inside the segment files
+#[derive(Clone, Copy, Debug)]
+struct Location {
+    /// The number of the segment file
+    segment: u64,
+    /// The byte offset of the record inside the segment file
+    offset: u64,
+    /// The index of the Domain Event inside the record
+    index: usize,
+    /// The version of the Domain Event
+    version: u32,
+}
+
+impl<A> FileEventStore<A>
+where
+    A: EventSourced,
+    A::Event: Serialize + DeserializeOwned,
+{
+    /// Opens the FileEventStore in the directory, creating the directory if it does not exist yet
+    /// # Arguments
+    /// * `directory` - The directory that contains the segment files
+    pub fn open<P>(directory: P) -> Result<Self, EventStoreError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_max_segment_bytes(directory, DEFAULT_MAX_SEGMENT_BYTES)
+    }
+
+    /// Opens the FileEventStore in the directory, creating the directory if it does not exist yet
+    /// # Arguments
+    /// * `directory` - The directory that contains the segment files
+    /// * `max_segment_bytes` - The size after which a new segment file is started
+    pub fn open_with_max_segment_bytes<P>(
+        directory: P,
+        max_segment_bytes: u64,
+    ) -> Result<Self, EventStoreError>
+    where
+        P: AsRef<Path>,
+    {
+        let directory = directory.as_ref().to_path_buf();
+        std::fs::create_dir_all(&directory).map_err(EventStoreError::storage)?;
+
+        let segments = list_segments(&directory)?;
+        let mut log = Vec::new();
+        let mut streams: HashMap<StreamId<A>, Vec<usize>> = HashMap::new();
+        let mut event_ids = HashSet::new();
+
+        for (segment_index, segment) in segments.iter().enumerate() {
+            let is_last = segment_index + 1 == segments.len();
+            let path = segment_path(&directory, *segment);
+            let bytes = std::fs::read(&path).map_err(EventStoreError::storage)?;
+            let mut offset = 0;
+
+            while offset < bytes.len() {
+                let Some(payload) = decode_record(&bytes[offset..]) else {
+                    if !is_last || !is_partial_record(&bytes[offset..]) {
+                        return Err(EventStoreError::storage(format!(
+                            "Corrupt record at offset {offset} of {}",
+                            path.display()
+                        )));
+                    }
+                    let file = OpenOptions::new()
+                        .write(true)
+                        .open(&path)
+                        .map_err(EventStoreError::storage)?;
+                    file.set_len(offset as u64)
+                        .map_err(EventStoreError::storage)?;
+                    file.sync_all().map_err(EventStoreError::storage)?;
+                    break;
+                };
+                let events: Vec<A::Event> =
+                    serde_json::from_slice(payload).map_err(EventStoreError::storage)?;
+                for (index, event) in events.iter().enumerate() {
+                    streams
+                        .entry(*event.aggregate_id())
+                        .or_default()
+                        .push(log.len());
+                    event_ids.insert(*event.id());
+                    log.push(Location {
+                        segment: *segment,
+                        offset: offset as u64,
+                        index,
+                        version: event.aggregate_version(),
+                    });
+                }
+                offset += RECORD_HEADER_BYTES + payload.len();
+            }
+        }
+
+        let number = segments.last().copied().unwrap_or(0);
+        let active = open_segment(&directory, number)?;
+
+        Ok(Self {
+            shared: Arc::new(Shared {
+                directory,
+                max_segment_bytes,
+                inner: Mutex::new(Inner {
+                    active,
+                    log,
+                    streams,
+                    event_ids,
+                    failed: false,
+                }),
+            }),
+        })
+    }
+
+    /// Runs the file I/O on the blocking thread pool of tokio
+    /// # Arguments
+    /// * `io` - The file I/O on the shared state of the FileEventStore
+    async fn run_blocking<T, F>(&self, io: F) -> Result<T, EventStoreError>
+    where
+        F: FnOnce(&Shared<A>) -> Result<T, EventStoreError> + Send + 'static,
+        T: Send + 'static,
+        A: 'static,
+        A::Event: Send,
+        StreamId<A>: Send,
+    {
+        let shared = self.shared.clone();
+        tokio::task::spawn_blocking(move || io(&shared))
+            .await
+            .map_err(EventStoreError::storage)?
+    }
+}
+
+impl<A> Shared<A>
+where
+    A: EventSourced,
+    A::Event: Serialize + DeserializeOwned,
+{
+    /// Reads the Domain Events at the locations, decoding every record only once
+    /// # Arguments
+    /// * `locations` - The locations of the Domain Events to read
+    fn read_locations<'a, I>(&self, locations: I) -> Result<Vec<Arc<A::Event>>, EventStoreError>
+    where
+        I: IntoIterator<Item = &'a Location>,
+    {
+        let mut events = Vec::new();
+        let mut record = None;
+        let mut decoded: Vec<Arc<A::Event>> = Vec::new();
+
+        for location in locations {
+            let key = (location.segment, location.offset);
+            if record != Some(key) {
+                decoded = read_record::<A::Event>(&self.directory, key.0, key.1)?
+                    .into_iter()
+                    .map(Arc::new)
+                    .collect();
+                record = Some(key);
+            }
+            let event = decoded.get(location.index).ok_or_else(|| {
+                EventStoreError::storage(format!(
+                    "Missing event {} in record at offset {} of segment {}",
+                    location.index, location.offset, location.segment
+                ))
+            })?;
+            events.push(event.clone());
+        }
+        Ok(events)
+    }
+
+    /// Appends the Domain Events to the active segment
+    /// # Arguments
+    /// * `aggregate_id` - The identifier of the stream
+    /// * `expected_version` - The version the stream is expected to have
+    /// * `events` - The Domain Events to append
+    fn append(
+        &self,
+        aggregate_id: &StreamId<A>,
+        expected_version: u32,
+        events: Vec<A::Event>,
+    ) -> Result<(), EventStoreError> {
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|err| EventStoreError::storage(err.to_string()))?;
+        if inner.failed {
+            return Err(EventStoreError::storage(
+                "A failed append left a partial record behind, the FileEventStore must be reopened",
+            ));
+        }
+
+        let actual_version = inner
+            .streams
+            .get(aggregate_id)
+            .and_then(|positions| positions.last())
+            .map_or(0, |position| inner.log[*position].version);
+        if actual_version != expected_version {
+            return Err(ConcurrencyConflict::new(
+                A::error_prefix(),
+                expected_version,
+                actual_version,
+            )
+            .into());
+        }
+
+        validate_append(aggregate_id, expected_version, &events)?;
+        let mut event_ids = HashSet::with_capacity(events.len());
+        for event in &events {
+            if inner.event_ids.contains(event.id()) || !event_ids.insert(*event.id()) {
+                return Err(EventStoreError::DuplicateEvent {
+                    event_id: *event.id(),
+                });
+            }
+        }
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let record = encode_record(&events)?;
+        if inner.active.len > 0 && inner.active.len + record.len() as u64 > self.max_segment_bytes {
+            inner.active = open_segment(&self.directory, inner.active.number + 1)?;
+            sync_directory(&self.directory)?;
+        }
+
+        let Inner {
+            active,
+            log,
+            streams,
+            event_ids: stored_event_ids,
+            failed,
+        } = &mut *inner;
+        let offset = active.len;
+        if let Err(err) = active
+            .file
+            .write_all(&record)
+            .and_then(|_| active.file.sync_data())
+        {
+            // A partial record in front of later records would make them unreadable, so the
+            // FileEventStore refuses appends if it cannot be removed
+            let truncated = active
+                .file
+                .set_len(offset)
+                .and_then(|_| active.file.sync_data())
+                .and_then(|_| active.file.metadata());
+            match truncated {
+                Ok(metadata) if metadata.len() == offset => {}
+                Ok(_) => *failed = true,
+                Err(truncate_err) => {
+                    *failed = true;
+                    return Err(EventStoreError::storage(format!(
+                        "{err}, and the partial record could not be removed: {truncate_err}"
+                    )));
+                }
+            }
+            return Err(EventStoreError::storage(err));
+        }
+        active.len += record.len() as u64;
+
+        let positions = streams.entry(*aggregate_id).or_default();
+        for (index, event) in events.iter().enumerate() {
+            positions.push(log.len());
+            log.push(Location {
+                segment: active.number,
+                offset,
+                index,
+                version: event.aggregate_version(),
+            });
+        }
+        stored_event_ids.extend(event_ids);
+        Ok(())
+    }
+
+    /// Reads the Domain Events of the stream from the version on
+    /// # Arguments
+    /// * `aggregate_id` - The identifier of the stream
+    /// * `from_version` - The first version to read
+    fn read_stream(
+        &self,
+        aggregate_id: &StreamId<A>,
+        from_version: u32,
+    ) -> Result<Vec<Arc<A::Event>>, EventStoreError> {
+        let locations: Vec<Location> = {
+            let inner = self
+                .inner
+                .lock()
+                .map_err(|err| EventStoreError::storage(err.to_string()))?;
+            inner
+                .streams
+                .get(aggregate_id)
+                .map(|positions| {
+                    positions
+                        .iter()
+                        .map(|position| inner.log[*position])
+                        .filter(|location| location.version >= from_version)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        self.read_locations(&locations)
+    }
+
+    /// Reads the Domain Events of every stream from the position on
+    /// # Arguments
+    /// * `from_position` - The first position to read
+    fn read_all(
+        &self,
+        from_position: u64,
+    ) -> Result<Vec<RecordedEvent<A::Event>>, EventStoreError> {
+        let locations: Vec<Location> = {
+            let inner = self
+                .inner
+                .lock()
+                .map_err(|err| EventStoreError::storage(err.to_string()))?;
+            inner
+                .log
+                .iter()
+                .skip(from_position as usize)
+                .copied()
+                .collect()
+        };
+        let events = self
+            .read_locations(&locations)?
+            .into_iter()
+            .enumerate()
+            .map(|(index, event)| RecordedEvent::new(from_position + index as u64, event))
+            .collect();
+        Ok(events)
+    }
+}
+
+#[async_trait::async_trait]
+impl<A> EventStore<A> for FileEventStore<A>
+where
+    A: EventSourced + 'static,
+    A::Event: Serialize + DeserializeOwned + Send + Sync + 'static,
+    StreamId<A>: Send + Sync,
+{
+    async fn append(
+        &self,
+        aggregate_id: &StreamId<A>,
+        expected_version: u32,
+        events: Vec<A::Event>,
+    ) -> Result<(), EventStoreError> {
+        let aggregate_id = *aggregate_id;
+        self.run_blocking(move |shared| shared.append(&aggregate_id, expected_version, events))
+            .await
+    }
+
+    async fn read_stream(
+        &self,
+        aggregate_id: &StreamId<A>,
+        from_version: u32,
+    ) -> Result<Vec<Arc<A::Event>>, EventStoreError> {
+        let aggregate_id = *aggregate_id;
+        self.run_blocking(move |shared| shared.read_stream(&aggregate_id, from_version))
+            .await
+    }
+
+    async fn read_all(
+        &self,
+        from_position: u64,
+    ) -> Result<Vec<RecordedEvent<A::Event>>, EventStoreError> {
+        self.run_blocking(move |shared| shared.read_all(from_position))
+            .await
+    }
+}
+
+/// The path of the segment file
+/// # Arguments
+/// * `directory` - The directory that contains the segment files
+/// * `number` - The number of the segment
+fn segment_path(directory: &Path, number: u64) -> PathBuf {
+    directory.join(format!("{number:020}.{SEGMENT_EXTENSION}"))
+}
+
+/// The numbers of the segment files in the directory in ascending order
+/// # Arguments
+/// * `directory` - The directory that contains the segment files
+fn list_segments(directory: &Path) -> Result<Vec<u64>, EventStoreError> {
+    let mut segments = Vec::new();
+    for entry in std::fs::read_dir(directory).map_err(EventStoreError::storage)? {
+        let path = entry.map_err(EventStoreError::storage)?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(SEGMENT_EXTENSION) {
+            continue;
+        }
+        if let Some(number) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse::<u64>().ok())
+        {
+            segments.push(number);
+        }
+    }
+    segments.sort_unstable();
+    Ok(segments)
+}
+
+/// Opens the segment file for appending, creating it if it does not exist yet
+/// # Arguments
+/// * `directory` - The directory that contains the segment files
+/// * `number` - The number of the segment
+fn open_segment(directory: &Path, number: u64) -> Result<ActiveSegment, EventStoreError> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(segment_path(directory, number))
+        .map_err(EventStoreError::storage)?;
+    let len = file.metadata().map_err(EventStoreError::storage)?.len();
+    Ok(ActiveSegment { number, file, len })
+}
+
+/// Makes the creation of a segment file durable
+/// # Arguments
+/// * `directory` - The directory that contains the segment files
+fn sync_directory(directory: &Path) -> Result<(), EventStoreError> {
+    #[cfg(unix)]
+    File::open(directory)
+        .and_then(|dir| dir.sync_all())
+        .map_err(EventStoreError::storage)?;
+    #[cfg(not(unix))]
+    let _ = directory;
+    Ok(())
+}
+
+/// Encodes the Domain Events as a single record
+/// # Arguments
+/// * `events` - The Domain Events of one append
+fn encode_record<E>(events: &[E]) -> Result<Vec<u8>, EventStoreError>
+where
+    E: Serialize,
+{
+    let payload = serde_json::to_vec(events).map_err(EventStoreError::storage)?;
+    let len = u32::try_from(payload.len())
+        .map_err(|_| EventStoreError::storage("The events are too large for a single record"))?;
+    let mut record = Vec::with_capacity(RECORD_HEADER_BYTES + payload.len());
+    record.extend_from_slice(&len.to_le_bytes());
+    record.extend_from_slice(&crc32(&payload).to_le_bytes());
+    record.extend_from_slice(&payload);
+    Ok(record)
+}
+
+/// Decodes the payload of the record at the start of the bytes. Returns None when the record is
+/// incomplete or its checksum does not match
+/// # Arguments
+/// * `bytes` - The bytes starting with a record
+fn decode_record(bytes: &[u8]) -> Option<&[u8]> {
+    let header = bytes.get(..RECORD_HEADER_BYTES)?;
+    let len = u32::from_le_bytes(header[..4].try_into().ok()?) as usize;
+    let checksum = u32::from_le_bytes(header[4..].try_into().ok()?);
+    let payload = bytes.get(RECORD_HEADER_BYTES..RECORD_HEADER_BYTES + len)?;
+    (crc32(payload) == checksum).then_some(payload)
+}
+
+/// Returns true if the record at the start of the bytes runs past their end, i.e. its header or
+/// its payload is incomplete
+/// # Arguments
+/// * `bytes` - The bytes starting with a record
+fn is_partial_record(bytes: &[u8]) -> bool {
+    match bytes.get(..4) {
+        Some(len) => {
+            let len = u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize;
+            RECORD_HEADER_BYTES.saturating_add(len) > bytes.len()
+        }
+        None => true,
+    }
+}
+
+/// Reads and decodes the record at the offset of the segment file
+/// # Arguments
+/// * `directory` - The directory that contains the segment files
+/// * `segment` - The number of the segment
+/// * `offset` - The byte offset of the record
+fn read_record<E>(directory: &Path, segment: u64, offset: u64) -> Result<Vec<E>, EventStoreError>
+where
+    E: DeserializeOwned,
+{
+    let mut file =
+        File::open(segment_path(directory, segment)).map_err(EventStoreError::storage)?;
+    file.seek(SeekFrom::Start(offset))
+        .map_err(EventStoreError::storage)?;
+    let mut header = [0u8; RECORD_HEADER_BYTES];
+    file.read_exact(&mut header)
+        .map_err(EventStoreError::storage)?;
+    let len = u32::from_le_bytes([header[0], header[1], header[2], header[3]]) as usize;
+    let mut record = header.to_vec();
+    record.resize(RECORD_HEADER_BYTES + len, 0);
+    file.read_exact(&mut record[RECORD_HEADER_BYTES..])
+        .map_err(EventStoreError::storage)?;
+    let payload = decode_record(&record).ok_or_else(|| {
+        EventStoreError::storage(format!(
+            "Corrupt record at offset {offset} of segment {segment}"
+        ))
+    })?;
+    serde_json::from_slice(payload).map_err(EventStoreError::storage)
+}
+
+/// The CRC-32 (IEEE) checksum of the bytes
+/// # Arguments
+/// * `bytes` - The bytes to checksum
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::building_blocks::{aggregate::Aggregate, entity::Entity, ids::AggregateId};
+    use chrono::{DateTime, Utc};
+    use serde::Deserialize;
+    use uuid::Uuid;
+
+    // The derive macros refer to the crate by its name
+    use crate as kern;
+
+    #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Serialize, Deserialize)]
+    struct AccountId(Uuid);
+
+    impl AggregateId for AccountId {}
+
+    #[derive(crate::Aggregate, Debug)]
+    struct Account {
+        #[entity_id]
+        id: AccountId,
+        #[field]
+        balance: i64,
+        version: u32,
+    }
+
+    #[derive(crate::DomainEvent, Debug, Serialize, Deserialize)]
+    struct Deposited {
+        id: EventId,
+        aggregate_id: AccountId,
+        aggregate_version: u32,
+        occurred_at: DateTime<Utc>,
+        amount: i64,
+    }
+
+    impl Deposited {
+        fn new(aggregate_id: AccountId, aggregate_version: u32, amount: i64) -> Self {
+            Self {
+                id: EventId::new_random_v4(),
+                aggregate_id,
+                aggregate_version,
+                occurred_at: Utc::now(),
+                amount,
+            }
+        }
+    }
+
+    impl EventSourced for Account {
+        type Event = Deposited;
+
+        fn apply(&mut self, event: &Deposited) {
+            self.balance += event.amount;
+        }
+    }
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            Self(std::env::temp_dir().join(format!("kern-file-event-store-{}", Uuid::new_v4())))
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn given_appended_events_when_reopening_then_the_events_are_restored() {
+        let dir = TempDir::new();
+        let id = AccountId(Uuid::new_v4());
+        {
+            let store = FileEventStore::<Account>::open(&dir.0).unwrap();
+            store
+                .append(
+                    &id,
+                    0,
+                    vec![Deposited::new(id, 1, 10), Deposited::new(id, 2, 5)],
+                )
+                .await
+                .unwrap();
+        }
+
+        let store = FileEventStore::<Account>::open(&dir.0).unwrap();
+        let events = store.read_stream(&id, 0).await.unwrap();
+        let account = Account {
+            id,
+            balance: 0,
+            version: 0,
+        }
+        .rehydrate(events.iter().map(|event| event.as_ref()))
+        .unwrap();
+
+        assert_eq!(*account.balance(), 15);
+        assert_eq!(account.version(), 2);
+        assert!(matches!(
+            store.append(&id, 1, vec![Deposited::new(id, 2, 1)]).await,
+            Err(EventStoreError::ConcurrencyConflict(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn given_a_small_segment_size_when_appending_then_segments_rotate() {
+        let dir = TempDir::new();
+        let a = AccountId(Uuid::new_v4());
+        let b = AccountId(Uuid::new_v4());
+        let store = FileEventStore::<Account>::open_with_max_segment_bytes(&dir.0, 64).unwrap();
+
+        store
+            .append(&a, 0, vec![Deposited::new(a, 1, 1)])
+            .await
+            .unwrap();
+        store
+            .append(&b, 0, vec![Deposited::new(b, 1, 2)])
+            .await
+            .unwrap();
+        store
+            .append(&a, 1, vec![Deposited::new(a, 2, 3)])
+            .await
+            .unwrap();
+        drop(store);
+
+        assert_eq!(list_segments(&dir.0).unwrap().len(), 3);
+
+        let store = FileEventStore::<Account>::open_with_max_segment_bytes(&dir.0, 64).unwrap();
+        let amounts: Vec<(u64, i64)> = store
+            .read_all(0)
+            .await
+            .unwrap()
+            .iter()
+            .map(|recorded| (recorded.position(), recorded.event().amount))
+            .collect();
+        assert_eq!(amounts, vec![(0, 1), (1, 2), (2, 3)]);
+        assert_eq!(store.read_stream(&a, 2).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn given_a_torn_write_when_reopening_then_the_partial_record_is_truncated() {
+        let dir = TempDir::new();
+        let id = AccountId(Uuid::new_v4());
+        {
+            let store = FileEventStore::<Account>::open(&dir.0).unwrap();
+            store
+                .append(&id, 0, vec![Deposited::new(id, 1, 10)])
+                .await
+                .unwrap();
+        }
+        let path = segment_path(&dir.0, 0);
+        let len = std::fs::metadata(&path).unwrap().len();
+        let torn = encode_record(&[Deposited::new(id, 2, 5)]).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&torn[..torn.len() / 2]).unwrap();
+        drop(file);
+
+        let store = FileEventStore::<Account>::open(&dir.0).unwrap();
+
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), len);
+        assert_eq!(store.read_stream(&id, 0).await.unwrap().len(), 1);
+        store
+            .append(&id, 1, vec![Deposited::new(id, 2, 5)])
+            .await
+            .unwrap();
+        assert_eq!(store.read_all(0).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn given_a_corrupt_record_before_others_when_reopening_then_opening_fails() {
+        let dir = TempDir::new();
+        let id = AccountId(Uuid::new_v4());
+        {
+            let store = FileEventStore::<Account>::open(&dir.0).unwrap();
+            for version in 1..=3 {
+                store
+                    .append(&id, version - 1, vec![Deposited::new(id, version, 1)])
+                    .await
+                    .unwrap();
+            }
+        }
+        let path = segment_path(&dir.0, 0);
+        let mut bytes = std::fs::read(&path).unwrap();
+        let first =
+            RECORD_HEADER_BYTES + u32::from_le_bytes(bytes[..4].try_into().unwrap()) as usize;
+        // Flips a byte of the payload of the second record
+        bytes[first + RECORD_HEADER_BYTES + 1] ^= 0xff;
+        std::fs::write(&path, &bytes).unwrap();
+
+        assert!(matches!(
+            FileEventStore::<Account>::open(&dir.0),
+            Err(EventStoreError::Storage(_))
+        ));
+        assert_eq!(std::fs::read(&path).unwrap(), bytes);
+    }
+
+    #[tokio::test]
+    async fn given_a_failed_append_when_it_cannot_be_rolled_back_then_appends_are_refused() {
+        let dir = TempDir::new();
+        let id = AccountId(Uuid::new_v4());
+        let store = FileEventStore::<Account>::open(&dir.0).unwrap();
+        store
+            .append(&id, 0, vec![Deposited::new(id, 1, 10)])
+            .await
+            .unwrap();
+        // A read-only handle fails both the write and the truncation
+        store.shared.inner.lock().unwrap().active.file =
+            File::open(segment_path(&dir.0, 0)).unwrap();
+
+        assert!(
+            store
+                .append(&id, 1, vec![Deposited::new(id, 2, 5)])
+                .await
+                .is_err()
+        );
+        assert!(matches!(
+            store.append(&id, 1, vec![Deposited::new(id, 2, 5)]).await,
+            Err(EventStoreError::Storage(err)) if err.to_string().contains("must be reopened")
+        ));
+        drop(store);
+
+        let store = FileEventStore::<Account>::open(&dir.0).unwrap();
+        store
+            .append(&id, 1, vec![Deposited::new(id, 2, 5)])
+            .await
+            .unwrap();
+        assert_eq!(store.read_stream(&id, 0).await.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+}
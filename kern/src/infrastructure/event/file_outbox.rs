@@ -0,0 +1,507 @@
+use std::{
+    collections::BTreeMap,
+    fs::{File, OpenOptions},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::application::{
+    error::outbox_error::OutboxError,
+    outbox::{Outbox, OutboxMessage},
+};
+
+/// A line of the outbox file
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Entry<E> {
+    Enqueued {
+        id: u64,
+        #[serde(default)]
+        attempts: u32,
+        #[serde(default)]
+        retry_at: Option<DateTime<Utc>>,
+        #[serde(default)]
+        parked: bool,
+        event: E,
+    },
+    Dispatched {
+        id: u64,
+    },
+    Failed {
+        id: u64,
+        #[serde(default)]
+        retry_at: Option<DateTime<Utc>>,
+    },
+    Parked {
+        id: u64,
+    },
+}
+
+/// The FileOutbox is an Outbox that persists its messages in an append-only file of JSON lines, so
+/// pending messages survive a process restart. Every change is fsynced before it returns.
+///
+/// When the FileOutbox is opened, a partial last line left behind by a torn write is truncated and
+/// the file is compacted to the messages that are still pending or parked. The file is also emptied
+/// whenever every message was dispatched.
+///
+/// The file I/O of the Outbox methods runs on the blocking thread pool of tokio, so it does not
+/// stall the async executor
+pub struct FileOutbox<E> {
+    path: PathBuf,
+    inner: Arc<Mutex<Inner<E>>>,
+}
+
+struct Inner<E> {
+    file: File,
+    next_id: u64,
+    pending: BTreeMap<u64, OutboxMessage<E>>,
+    parked: BTreeMap<u64, OutboxMessage<E>>,
+}
+
+impl<E> FileOutbox<E>
+where
+    E: Serialize + DeserializeOwned,
+{
+    /// Opens the FileOutbox, creating the file if it does not exist yet
+    /// # Arguments
+    /// * `path` - The path of the outbox file
+    pub fn open<P>(path: P) -> Result<Self, OutboxError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent).map_err(OutboxError::storage)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)
+            .map_err(OutboxError::storage)?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).map_err(OutboxError::storage)?;
+
+        // Everything after the last newline is a torn write
+        let valid = bytes
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |position| position + 1);
+
+        let mut next_id = 1;
+        let mut lines = 0;
+        let mut pending = BTreeMap::new();
+        let mut parked = BTreeMap::new();
+        for line in bytes[..valid].split(|byte| *byte == b'\n') {
+            if line.is_empty() {
+                continue;
+            }
+            lines += 1;
+            match serde_json::from_slice::<Entry<E>>(line).map_err(OutboxError::storage)? {
+                Entry::Enqueued {
+                    id,
+                    attempts,
+                    retry_at,
+                    parked: is_parked,
+                    event,
+                } => {
+                    next_id = next_id.max(id + 1);
+                    let message =
+                        OutboxMessage::new(id, attempts, Arc::new(event)).with_retry_at(retry_at);
+                    match is_parked {
+                        true => parked.insert(id, message),
+                        false => pending.insert(id, message),
+                    };
+                }
+                Entry::Dispatched { id } => {
+                    pending.remove(&id);
+                }
+                Entry::Failed { id, retry_at } => {
+                    if let Some(message) = pending.get_mut(&id) {
+                        *message = failed(message, retry_at);
+                    }
+                }
+                Entry::Parked { id } => {
+                    if let Some(message) = pending.remove(&id) {
+                        parked.insert(id, failed(&message, None));
+                    }
+                }
+            }
+        }
+
+        if valid < bytes.len() || lines != pending.len() + parked.len() {
+            file = compact(&path, &pending, &parked)?;
+        }
+
+        Ok(Self {
+            path,
+            inner: Arc::new(Mutex::new(Inner {
+                file,
+                next_id,
+                pending,
+                parked,
+            })),
+        })
+    }
+
+    /// The path of the outbox file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl<E> FileOutbox<E>
+where
+    E: Send + Sync + 'static,
+{
+    /// Runs the file I/O with the locked state on the blocking thread pool of tokio
+    /// # Arguments
+    /// * `io` - The file I/O on the state of the FileOutbox
+    async fn run_blocking<T, F>(&self, io: F) -> Result<T, OutboxError>
+    where
+        F: FnOnce(&mut Inner<E>) -> Result<T, OutboxError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut inner = inner
+                .lock()
+                .map_err(|err| OutboxError::storage(err.to_string()))?;
+            io(&mut inner)
+        })
+        .await
+        .map_err(OutboxError::storage)?
+    }
+}
+
+/// Appends the lines to the outbox file and fsyncs it
+/// # Arguments
+/// * `file` - The outbox file
+/// * `lines` - The newline terminated lines
+fn append(file: &mut File, lines: &[u8]) -> Result<(), OutboxError> {
+    file.write_all(lines)
+        .and_then(|_| file.sync_data())
+        .map_err(OutboxError::storage)
+}
+
+/// The message after one more failed attempt
+/// # Arguments
+/// * `message` - The message that failed to publish
+/// * `retry_at` - The earliest time the message is published again
+fn failed<E>(message: &OutboxMessage<E>, retry_at: Option<DateTime<Utc>>) -> OutboxMessage<E> {
+    OutboxMessage::new(
+        message.id(),
+        message.attempts() + 1,
+        message.event().clone(),
+    )
+    .with_retry_at(retry_at)
+}
+
+/// Encodes an enqueued message as a newline terminated line
+/// # Arguments
+/// * `message` - The enqueued message
+/// * `parked` - Whether the message is parked
+fn enqueued_line<E>(message: &OutboxMessage<E>, parked: bool) -> Result<Vec<u8>, OutboxError>
+where
+    E: Serialize,
+{
+    let mut line = serde_json::to_vec(&serde_json::json!({
+        "type": "enqueued",
+        "id": message.id(),
+        "attempts": message.attempts(),
+        "retry_at": message.retry_at(),
+        "parked": parked,
+        "event": message.event().as_ref(),
+    }))
+    .map_err(OutboxError::storage)?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+/// Rewrites the outbox file with only the pending and parked messages and returns it opened for
+/// appending
+/// # Arguments
+/// * `path` - The path of the outbox file
+/// * `pending` - The pending messages
+/// * `parked` - The parked messages
+fn compact<E>(
+    path: &Path,
+    pending: &BTreeMap<u64, OutboxMessage<E>>,
+    parked: &BTreeMap<u64, OutboxMessage<E>>,
+) -> Result<File, OutboxError>
+where
+    E: Serialize,
+{
+    let mut lines = Vec::new();
+    let pending = pending.values().map(|message| (message, false));
+    let parked = parked.values().map(|message| (message, true));
+    let mut messages: Vec<_> = pending.chain(parked).collect();
+    messages.sort_by_key(|(message, _)| message.id());
+    for (message, parked) in messages {
+        lines.extend(enqueued_line(message, parked)?);
+    }
+    let compacted = path.with_extension("compacting");
+    let mut file = File::create(&compacted).map_err(OutboxError::storage)?;
+    append(&mut file, &lines)?;
+    std::fs::rename(&compacted, path).map_err(OutboxError::storage)?;
+    #[cfg(unix)]
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        File::open(parent)
+            .and_then(|dir| dir.sync_all())
+            .map_err(OutboxError::storage)?;
+    }
+    OpenOptions::new()
+        .append(true)
+        .open(path)
+        .map_err(OutboxError::storage)
+}
+
+#[async_trait::async_trait]
+impl<E> Outbox<E> for FileOutbox<E>
+where
+    E: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    async fn enqueue(&self, events: Vec<E>) -> Result<(), OutboxError> {
+        self.run_blocking(move |inner| {
+            let mut lines = Vec::new();
+            let messages: Vec<OutboxMessage<E>> = events
+                .into_iter()
+                .enumerate()
+                .map(|(index, event)| {
+                    OutboxMessage::new(inner.next_id + index as u64, 0, event.into())
+                })
+                .collect();
+            for message in &messages {
+                lines.extend(enqueued_line(message, false)?);
+            }
+            append(&mut inner.file, &lines)?;
+
+            inner.next_id += messages.len() as u64;
+            inner
+                .pending
+                .extend(messages.into_iter().map(|message| (message.id(), message)));
+            Ok(())
+        })
+        .await
+    }
+
+    async fn pending(&self, limit: usize) -> Result<Vec<OutboxMessage<E>>, OutboxError> {
+        let inner = self
+            .inner
+            .lock()
+            .map_err(|err| OutboxError::storage(err.to_string()))?;
+        let now = Utc::now();
+        Ok(inner
+            .pending
+            .values()
+            .filter(|message| message.is_due(&now))
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn mark_dispatched(&self, id: u64) -> Result<(), OutboxError> {
+        self.run_blocking(move |inner| {
+            if !inner.pending.contains_key(&id) {
+                return Err(OutboxError::UnknownMessage { id });
+            }
+
+            if inner.pending.len() == 1 && inner.parked.is_empty() {
+                inner
+                    .file
+                    .set_len(0)
+                    .and_then(|_| inner.file.sync_data())
+                    .map_err(OutboxError::storage)?;
+            } else {
+                append(
+                    &mut inner.file,
+                    format!("{{\"type\":\"dispatched\",\"id\":{id}}}\n").as_bytes(),
+                )?;
+            }
+            inner.pending.remove(&id);
+            Ok(())
+        })
+        .await
+    }
+
+    async fn mark_failed(&self, id: u64, retry_at: DateTime<Utc>) -> Result<(), OutboxError> {
+        self.run_blocking(move |inner| {
+            let Some(message) = inner.pending.get(&id).cloned() else {
+                return Err(OutboxError::UnknownMessage { id });
+            };
+
+            let mut line = serde_json::to_vec(&serde_json::json!({
+                "type": "failed",
+                "id": id,
+                "retry_at": retry_at,
+            }))
+            .map_err(OutboxError::storage)?;
+            line.push(b'\n');
+            append(&mut inner.file, &line)?;
+            inner.pending.insert(id, failed(&message, Some(retry_at)));
+            Ok(())
+        })
+        .await
+    }
+
+    async fn mark_parked(&self, id: u64) -> Result<(), OutboxError> {
+        self.run_blocking(move |inner| {
+            let Some(message) = inner.pending.get(&id).cloned() else {
+                return Err(OutboxError::UnknownMessage { id });
+            };
+
+            append(
+                &mut inner.file,
+                format!("{{\"type\":\"parked\",\"id\":{id}}}\n").as_bytes(),
+            )?;
+            inner.pending.remove(&id);
+            inner.parked.insert(id, failed(&message, None));
+            Ok(())
+        })
+        .await
+    }
+
+    async fn parked(&self, limit: usize) -> Result<Vec<OutboxMessage<E>>, OutboxError> {
+        let inner = self
+            .inner
+            .lock()
+            .map_err(|err| OutboxError::storage(err.to_string()))?;
+        Ok(inner.parked.values().take(limit).cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Deposited {
+        amount: i64,
+    }
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            Self(std::env::temp_dir().join(format!("kern-file-outbox-{}", Uuid::new_v4())))
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn given_pending_messages_when_reopening_then_only_undispatched_messages_remain() {
+        let dir = TempDir::new();
+        let path = dir.0.join("outbox.jsonl");
+        {
+            let outbox = FileOutbox::<Deposited>::open(&path).unwrap();
+            outbox
+                .enqueue(vec![
+                    Deposited { amount: 1 },
+                    Deposited { amount: 2 },
+                    Deposited { amount: 3 },
+                ])
+                .await
+                .unwrap();
+            outbox.mark_dispatched(1).await.unwrap();
+            outbox.mark_failed(2, Utc::now()).await.unwrap();
+        }
+
+        let outbox = FileOutbox::<Deposited>::open(&path).unwrap();
+        let pending = outbox.pending(10).await.unwrap();
+
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].id(), 2);
+        assert_eq!(pending[0].attempts(), 1);
+        assert!(pending[0].retry_at().is_some());
+        assert_eq!(pending[1].event().amount, 3);
+
+        outbox.enqueue(vec![Deposited { amount: 4 }]).await.unwrap();
+        assert_eq!(outbox.pending(10).await.unwrap()[2].id(), 4);
+    }
+
+    #[tokio::test]
+    async fn given_failed_and_parked_messages_when_reopening_then_they_are_not_pending() {
+        let dir = TempDir::new();
+        let path = dir.0.join("outbox.jsonl");
+        {
+            let outbox = FileOutbox::<Deposited>::open(&path).unwrap();
+            outbox
+                .enqueue(vec![
+                    Deposited { amount: 1 },
+                    Deposited { amount: 2 },
+                    Deposited { amount: 3 },
+                ])
+                .await
+                .unwrap();
+            let later = Utc::now() + chrono::Duration::hours(1);
+            outbox.mark_failed(1, later).await.unwrap();
+            outbox.mark_parked(2).await.unwrap();
+            outbox.mark_dispatched(3).await.unwrap();
+        }
+
+        // Opening twice also reads the compacted file
+        drop(FileOutbox::<Deposited>::open(&path).unwrap());
+        let outbox = FileOutbox::<Deposited>::open(&path).unwrap();
+
+        assert!(outbox.pending(10).await.unwrap().is_empty());
+        let parked = outbox.parked(10).await.unwrap();
+        assert_eq!(parked.len(), 1);
+        assert_eq!(parked[0].id(), 2);
+        assert_eq!(parked[0].attempts(), 1);
+        assert!(matches!(
+            outbox.mark_dispatched(2).await,
+            Err(OutboxError::UnknownMessage { id: 2 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn given_a_torn_write_when_reopening_then_the_partial_line_is_truncated() {
+        let dir = TempDir::new();
+        let path = dir.0.join("outbox.jsonl");
+        {
+            let outbox = FileOutbox::<Deposited>::open(&path).unwrap();
+            outbox.enqueue(vec![Deposited { amount: 1 }]).await.unwrap();
+        }
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"type\":\"enqueued\",\"id\":2,\"ev")
+            .unwrap();
+        drop(file);
+
+        let outbox = FileOutbox::<Deposited>::open(&path).unwrap();
+
+        assert_eq!(outbox.pending(10).await.unwrap().len(), 1);
+        assert!(std::fs::read(&path).unwrap().ends_with(b"\n"));
+    }
+
+    #[tokio::test]
+    async fn given_every_message_dispatched_when_marking_then_the_file_is_emptied() {
+        let dir = TempDir::new();
+        let path = dir.0.join("outbox.jsonl");
+        let outbox = FileOutbox::<Deposited>::open(&path).unwrap();
+        outbox.enqueue(vec![Deposited { amount: 1 }]).await.unwrap();
+
+        outbox.mark_dispatched(1).await.unwrap();
+
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 0);
+        assert!(matches!(
+            outbox.mark_dispatched(1).await,
+            Err(OutboxError::UnknownMessage { id: 1 })
+        ));
+    }
+}
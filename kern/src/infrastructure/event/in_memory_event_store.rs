@@ -0,0 +1,335 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, RwLock},
+};
+
+use crate::{
+    application::{
+        error::{concurrency_conflict::ConcurrencyConflict, event_store_error::EventStoreError},
+        event_store::{EventStore, RecordedEvent, StreamId, validate_append},
+    },
+    building_blocks::{domain_event::DomainEvent, event_sourced::EventSourced, ids::EventId},
+};
+
+/// The InMemoryEventStore is an EventStore that keeps the Domain Events in memory. Useful for tests
+/// and prototypes that do not need the Domain Events to outlive the process
+pub struct InMemoryEventStore<A>
+where
+    A: EventSourced,
+{
+    inner: RwLock<Inner<A::Event>>,
+}
+
+struct Inner<E>
+where
+    E: DomainEvent,
+{
+    /// Every Domain Event in the order it was appended
+    log: Vec<Arc<E>>,
+    /// The positions in the log of each stream's Domain Events
+    streams: HashMap<E::Id, Vec<usize>>,
+    /// The identifiers of every appended Domain Event
+    event_ids: HashSet<EventId>,
+}
+
+impl<A> InMemoryEventStore<A>
+where
+    A: EventSourced,
+{
+    /// Creates a new, empty InMemoryEventStore
+    pub fn new() -> Self {
+        Self {
+            inner: RwLock::new(Inner {
+                log: Vec::new(),
+                streams: HashMap::new(),
+                event_ids: HashSet::new(),
+            }),
+        }
+    }
+}
+
+impl<A> Default for InMemoryEventStore<A>
+where
+    A: EventSourced,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl<A> EventStore<A> for InMemoryEventStore<A>
+where
+    A: EventSourced,
+    A::Event: Send + Sync + 'static,
+    StreamId<A>: Send + Sync,
+{
+    async fn append(
+        &self,
+        aggregate_id: &StreamId<A>,
+        expected_version: u32,
+        events: Vec<A::Event>,
+    ) -> Result<(), EventStoreError> {
+        let mut inner = self
+            .inner
+            .write()
+            .map_err(|err| EventStoreError::storage(err.to_string()))?;
+
+        let actual_version = inner
+            .streams
+            .get(aggregate_id)
+            .and_then(|positions| positions.last())
+            .map_or(0, |position| inner.log[*position].aggregate_version());
+        if actual_version != expected_version {
+            return Err(ConcurrencyConflict::new(
+                A::error_prefix(),
+                expected_version,
+                actual_version,
+            )
+            .into());
+        }
+
+        validate_append(aggregate_id, expected_version, &events)?;
+        let mut event_ids = HashSet::with_capacity(events.len());
+        for event in &events {
+            if inner.event_ids.contains(event.id()) || !event_ids.insert(*event.id()) {
+                return Err(EventStoreError::DuplicateEvent {
+                    event_id: *event.id(),
+                });
+            }
+        }
+
+        let Inner {
+            log,
+            streams,
+            event_ids: stored_event_ids,
+        } = &mut *inner;
+        let positions = streams.entry(*aggregate_id).or_default();
+        for event in events {
+            positions.push(log.len());
+            log.push(Arc::new(event));
+        }
+        stored_event_ids.extend(event_ids);
+        Ok(())
+    }
+
+    async fn read_stream(
+        &self,
+        aggregate_id: &StreamId<A>,
+        from_version: u32,
+    ) -> Result<Vec<Arc<A::Event>>, EventStoreError> {
+        let inner = self
+            .inner
+            .read()
+            .map_err(|err| EventStoreError::storage(err.to_string()))?;
+        let events = inner
+            .streams
+            .get(aggregate_id)
+            .map(|positions| {
+                positions
+                    .iter()
+                    .map(|position| inner.log[*position].clone())
+                    .filter(|event| event.aggregate_version() >= from_version)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(events)
+    }
+
+    async fn read_all(
+        &self,
+        from_position: u64,
+    ) -> Result<Vec<RecordedEvent<A::Event>>, EventStoreError> {
+        let inner = self
+            .inner
+            .read()
+            .map_err(|err| EventStoreError::storage(err.to_string()))?;
+        let events = inner
+            .log
+            .iter()
+            .enumerate()
+            .skip(from_position as usize)
+            .map(|(position, event)| RecordedEvent::new(position as u64, event.clone()))
+            .collect();
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::building_blocks::{aggregate::Aggregate, entity::Entity};
+    use chrono::{DateTime, Utc};
+    use uuid::Uuid;
+
+    // The derive macros refer to the crate by its name
+    use crate as kern;
+
+    #[derive(crate::Aggregate, Debug)]
+    struct Account {
+        #[generate_id(Uuid)]
+        #[entity_id]
+        id: AccountId,
+        #[field]
+        balance: i64,
+        version: u32,
+    }
+
+    #[derive(crate::DomainEvent, Debug)]
+    struct Deposited {
+        id: EventId,
+        aggregate_id: AccountId,
+        aggregate_version: u32,
+        occurred_at: DateTime<Utc>,
+        amount: i64,
+    }
+
+    impl Deposited {
+        fn new(aggregate_id: AccountId, aggregate_version: u32, amount: i64) -> Self {
+            Self {
+                id: EventId::new_random_v4(),
+                aggregate_id,
+                aggregate_version,
+                occurred_at: Utc::now(),
+                amount,
+            }
+        }
+    }
+
+    impl EventSourced for Account {
+        type Event = Deposited;
+
+        fn apply(&mut self, event: &Deposited) {
+            self.balance += event.amount;
+        }
+    }
+
+    #[tokio::test]
+    async fn given_appended_events_when_reading_the_stream_then_the_aggregate_is_rehydrated() {
+        let store = InMemoryEventStore::<Account>::new();
+        let id = AccountId::new(Uuid::new_v4());
+
+        store
+            .append(
+                &id,
+                0,
+                vec![Deposited::new(id, 1, 10), Deposited::new(id, 2, 5)],
+            )
+            .await
+            .unwrap();
+        store
+            .append(&id, 2, vec![Deposited::new(id, 3, 20)])
+            .await
+            .unwrap();
+
+        let events = store.read_stream(&id, 0).await.unwrap();
+        let account = Account {
+            id,
+            balance: 0,
+            version: 0,
+        }
+        .rehydrate(events.iter().map(|event| event.as_ref()))
+        .unwrap();
+
+        assert_eq!(*account.balance(), 35);
+        assert_eq!(account.version(), 3);
+        assert_eq!(store.read_stream(&id, 3).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn given_a_stale_expected_version_when_appending_then_a_conflict_is_returned() {
+        let store = InMemoryEventStore::<Account>::new();
+        let id = AccountId::new(Uuid::new_v4());
+        store
+            .append(&id, 0, vec![Deposited::new(id, 1, 10)])
+            .await
+            .unwrap();
+
+        let result = store.append(&id, 0, vec![Deposited::new(id, 1, 10)]).await;
+
+        assert!(matches!(
+            result,
+            Err(EventStoreError::ConcurrencyConflict(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn given_gaps_or_duplicates_when_appending_then_the_events_are_rejected() {
+        let store = InMemoryEventStore::<Account>::new();
+        let id = AccountId::new(Uuid::new_v4());
+
+        let gap = store
+            .append(
+                &id,
+                0,
+                vec![Deposited::new(id, 1, 10), Deposited::new(id, 3, 10)],
+            )
+            .await;
+        assert!(matches!(
+            gap,
+            Err(EventStoreError::NonContiguousVersion {
+                expected_version: 2,
+                actual_version: 3
+            })
+        ));
+
+        let overflow = validate_append(&id, u32::MAX, &[Deposited::new(id, 0, 10)]);
+        assert!(matches!(
+            overflow,
+            Err(EventStoreError::VersionOverflow { .. })
+        ));
+
+        let event = Deposited::new(id, 1, 10);
+        let duplicate = Deposited {
+            id: event.id,
+            aggregate_id: id,
+            aggregate_version: 2,
+            occurred_at: Utc::now(),
+            amount: 10,
+        };
+        let result = store.append(&id, 0, vec![event, duplicate]).await;
+        assert!(matches!(
+            result,
+            Err(EventStoreError::DuplicateEvent { .. })
+        ));
+
+        let other = AccountId::new(Uuid::new_v4());
+        let result = store
+            .append(&id, 0, vec![Deposited::new(other, 1, 10)])
+            .await;
+        assert!(matches!(
+            result,
+            Err(EventStoreError::AggregateMismatch { .. })
+        ));
+
+        assert!(store.read_all(0).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn given_several_streams_when_reading_all_then_events_are_in_global_order() {
+        let store = InMemoryEventStore::<Account>::new();
+        let a = AccountId::new(Uuid::new_v4());
+        let b = AccountId::new(Uuid::new_v4());
+
+        store
+            .append(&a, 0, vec![Deposited::new(a, 1, 1)])
+            .await
+            .unwrap();
+        store
+            .append(&b, 0, vec![Deposited::new(b, 1, 2)])
+            .await
+            .unwrap();
+        store
+            .append(&a, 1, vec![Deposited::new(a, 2, 3)])
+            .await
+            .unwrap();
+
+        let events = store.read_all(1).await.unwrap();
+        let amounts: Vec<(u64, i64)> = events
+            .iter()
+            .map(|recorded| (recorded.position(), recorded.event().amount))
+            .collect();
+
+        assert_eq!(amounts, vec![(1, 2), (2, 3)]);
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::application::{
+    error::outbox_error::OutboxError,
+    outbox::{Outbox, OutboxMessage},
+};
+
+/// The InMemoryOutbox is an Outbox that keeps the pending messages in memory. It does not survive
+/// a process restart, which makes it useful for tests and prototypes only
+pub struct InMemoryOutbox<E> {
+    inner: Mutex<Inner<E>>,
+}
+
+struct Inner<E> {
+    next_id: u64,
+    pending: BTreeMap<u64, OutboxMessage<E>>,
+    parked: BTreeMap<u64, OutboxMessage<E>>,
+}
+
+impl<E> InMemoryOutbox<E> {
+    /// Creates a new, empty InMemoryOutbox
+    pub fn new() -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                next_id: 1,
+                pending: BTreeMap::new(),
+                parked: BTreeMap::new(),
+            }),
+        }
+    }
+}
+
+impl<E> Default for InMemoryOutbox<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl<E> Outbox<E> for InMemoryOutbox<E>
+where
+    E: Send + Sync + 'static,
+{
+    async fn enqueue(&self, events: Vec<E>) -> Result<(), OutboxError> {
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|err| OutboxError::storage(err.to_string()))?;
+        for event in events {
+            let id = inner.next_id;
+            inner.next_id += 1;
+            inner
+                .pending
+                .insert(id, OutboxMessage::new(id, 0, Arc::new(event)));
+        }
+        Ok(())
+    }
+
+    async fn pending(&self, limit: usize) -> Result<Vec<OutboxMessage<E>>, OutboxError> {
+        let inner = self
+            .inner
+            .lock()
+            .map_err(|err| OutboxError::storage(err.to_string()))?;
+        let now = Utc::now();
+        Ok(inner
+            .pending
+            .values()
+            .filter(|message| message.is_due(&now))
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn mark_dispatched(&self, id: u64) -> Result<(), OutboxError> {
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|err| OutboxError::storage(err.to_string()))?;
+        inner
+            .pending
+            .remove(&id)
+            .map(|_| ())
+            .ok_or(OutboxError::UnknownMessage { id })
+    }
+
+    async fn mark_failed(&self, id: u64, retry_at: DateTime<Utc>) -> Result<(), OutboxError> {
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|err| OutboxError::storage(err.to_string()))?;
+        let message = inner
+            .pending
+            .get_mut(&id)
+            .ok_or(OutboxError::UnknownMessage { id })?;
+        *message = OutboxMessage::new(id, message.attempts() + 1, message.event().clone())
+            .with_retry_at(Some(retry_at));
+        Ok(())
+    }
+
+    async fn mark_parked(&self, id: u64) -> Result<(), OutboxError> {
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|err| OutboxError::storage(err.to_string()))?;
+        let message = inner
+            .pending
+            .remove(&id)
+            .ok_or(OutboxError::UnknownMessage { id })?;
+        inner.parked.insert(
+            id,
+            OutboxMessage::new(id, message.attempts() + 1, message.event().clone()),
+        );
+        Ok(())
+    }
+
+    async fn parked(&self, limit: usize) -> Result<Vec<OutboxMessage<E>>, OutboxError> {
+        let inner = self
+            .inner
+            .lock()
+            .map_err(|err| OutboxError::storage(err.to_string()))?;
+        Ok(inner.parked.values().take(limit).cloned().collect())
+    }
+}
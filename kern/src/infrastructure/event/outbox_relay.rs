@@ -0,0 +1,370 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use tokio::sync::watch;
+
+use crate::{
+    application::{error::outbox_error::OutboxError, event::EventPublisher, outbox::Outbox},
+    building_blocks::domain_event::DynDomainEvent,
+};
+
+/// The default number of messages the OutboxRelay publishes per run
+pub const DEFAULT_BATCH_SIZE: usize = 100;
+
+/// The default number of attempts to publish a message before it is parked
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 10;
+
+/// The default delay before a message is published again after its first failed attempt
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The default upper limit of the delay before a message is published again
+pub const DEFAULT_MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+/// The OutboxRelay publishes the pending messages of an Outbox to a topic of an EventPublisher in
+/// a background process.
+///
+/// Messages are published in the order they were enqueued. A message that fails to publish stays
+/// pending and is retried after a delay that doubles with every failed attempt, while the relay
+/// moves on to the later messages, so a failed message is published after messages that were
+/// enqueued after it. A message that failed the maximum number of attempts is parked in the
+/// Outbox and not published again
+pub struct OutboxRelay<E>
+where
+    E: Send + Sync + 'static,
+{
+    relay: Relay<E>,
+    shutdown_tx: watch::Sender<bool>,
+}
+
+struct Relay<E>
+where
+    E: Send + Sync + 'static,
+{
+    outbox: Arc<dyn Outbox<E>>,
+    publisher: Arc<dyn EventPublisher>,
+    topic: &'static str,
+    batch_size: usize,
+    max_attempts: u32,
+    retry_delay: Duration,
+    max_retry_delay: Duration,
+}
+
+impl<E> Clone for Relay<E>
+where
+    E: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            outbox: self.outbox.clone(),
+            publisher: self.publisher.clone(),
+            topic: self.topic,
+            batch_size: self.batch_size,
+            max_attempts: self.max_attempts,
+            retry_delay: self.retry_delay,
+            max_retry_delay: self.max_retry_delay,
+        }
+    }
+}
+
+impl<E> Relay<E>
+where
+    E: DynDomainEvent + 'static,
+{
+    async fn relay_once(&self) -> Result<usize, OutboxError> {
+        let mut dispatched = 0;
+        for message in self.outbox.pending(self.batch_size).await? {
+            let event: Arc<dyn DynDomainEvent> = message.event().clone();
+            if self.publisher.try_publish(self.topic, event).is_ok() {
+                self.outbox.mark_dispatched(message.id()).await?;
+                dispatched += 1;
+                continue;
+            }
+
+            let attempts = message.attempts().saturating_add(1);
+            if attempts >= self.max_attempts {
+                self.outbox.mark_parked(message.id()).await?;
+            } else {
+                let delay = chrono::Duration::from_std(self.retry_delay(attempts))
+                    .unwrap_or(chrono::Duration::MAX);
+                let retry_at = Utc::now()
+                    .checked_add_signed(delay)
+                    .unwrap_or(DateTime::<Utc>::MAX_UTC);
+                self.outbox.mark_failed(message.id(), retry_at).await?;
+            }
+        }
+        Ok(dispatched)
+    }
+
+    /// The delay before a message is published again, which doubles with every failed attempt up
+    /// to the maximum delay
+    /// # Arguments
+    /// * `attempts` - The number of failed attempts to publish the message
+    fn retry_delay(&self, attempts: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(attempts.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.retry_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_retry_delay)
+            .min(self.max_retry_delay)
+    }
+}
+
+impl<E> OutboxRelay<E>
+where
+    E: DynDomainEvent + 'static,
+{
+    /// Creates a new OutboxRelay
+    /// # Arguments
+    /// * `outbox` - The Outbox whose messages are published
+    /// * `publisher` - The publisher the messages are published with
+    /// * `topic` - The topic the messages are published to
+    pub fn new(
+        outbox: Arc<dyn Outbox<E>>,
+        publisher: Arc<dyn EventPublisher>,
+        topic: &'static str,
+    ) -> Self {
+        let (tx, _) = watch::channel(false);
+        Self {
+            relay: Relay {
+                outbox,
+                publisher,
+                topic,
+                batch_size: DEFAULT_BATCH_SIZE,
+                max_attempts: DEFAULT_MAX_ATTEMPTS,
+                retry_delay: DEFAULT_RETRY_DELAY,
+                max_retry_delay: DEFAULT_MAX_RETRY_DELAY,
+            },
+            shutdown_tx: tx,
+        }
+    }
+
+    /// Sets the number of messages that are published per run
+    /// # Arguments
+    /// * `batch_size` - The maximum number of messages published per run, at least 1
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.relay.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets the number of attempts to publish a message before it is parked
+    /// # Arguments
+    /// * `max_attempts` - The maximum number of attempts, including the first one, at least 1
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.relay.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before a message is published again after a failed attempt
+    /// # Arguments
+    /// * `retry_delay` - The delay after the first failed attempt, which doubles with every
+    ///   further failed attempt
+    /// * `max_retry_delay` - The upper limit of the delay
+    pub fn with_retry_delay(mut self, retry_delay: Duration, max_retry_delay: Duration) -> Self {
+        self.relay.retry_delay = retry_delay;
+        self.relay.max_retry_delay = max_retry_delay;
+        self
+    }
+
+    /// Publishes the pending messages once and returns the number of dispatched messages
+    pub async fn relay_once(&self) -> Result<usize, OutboxError> {
+        self.relay.relay_once().await
+    }
+
+    /// Starts publishing the pending messages in a background process, polling the Outbox
+    /// whenever it was drained
+    /// # Arguments
+    /// * `interval` - The time to wait before polling the Outbox again
+    pub fn start(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let relay = self.relay.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                let wait = match relay.relay_once().await {
+                    Ok(dispatched) if dispatched == relay.batch_size => Duration::ZERO,
+                    _ => interval,
+                };
+
+                tokio::select! {
+                    _ = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() { break; }
+                    }
+
+                    _ = tokio::time::sleep(wait) => {}
+                }
+            }
+        })
+    }
+
+    /// Sends a shutdown signal to the background process
+    pub fn shutdown(&self) {
+        let _ = self.shutdown_tx.send(true);
+    }
+}
+
+#[cfg(all(test, feature = "in_memory"))]
+mod test {
+    use std::sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    };
+
+    use super::*;
+    use crate::{
+        application::error::publish_error::PublishError,
+        building_blocks::{
+            domain_event::DomainEvent,
+            ids::{AggregateId, EventId},
+        },
+        infrastructure::event::in_memory_outbox::InMemoryOutbox,
+    };
+    use chrono::{DateTime, Utc};
+    use uuid::Uuid;
+
+    #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+    struct AccountId(Uuid);
+
+    impl AggregateId for AccountId {}
+
+    #[derive(Debug)]
+    struct Deposited {
+        id: EventId,
+        aggregate_id: AccountId,
+        occurred_at: DateTime<Utc>,
+    }
+
+    impl DomainEvent for Deposited {
+        type Id = AccountId;
+        fn id(&self) -> &EventId {
+            &self.id
+        }
+        fn aggregate_id(&self) -> &AccountId {
+            &self.aggregate_id
+        }
+        fn aggregate_version(&self) -> u32 {
+            1
+        }
+        fn occurred_at(&self) -> &DateTime<Utc> {
+            &self.occurred_at
+        }
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn deposited() -> Deposited {
+        Deposited {
+            id: EventId::new_random_v4(),
+            aggregate_id: AccountId(Uuid::new_v4()),
+            occurred_at: Utc::now(),
+        }
+    }
+
+    #[derive(Default)]
+    struct FlakyPublisher {
+        failing: AtomicBool,
+        rejected: Mutex<Option<EventId>>,
+        published: Mutex<Vec<EventId>>,
+    }
+
+    impl EventPublisher for FlakyPublisher {
+        fn publish(&self, topic: &'static str, event: Arc<dyn DynDomainEvent>) {
+            let _ = self.try_publish(topic, event);
+        }
+
+        fn try_publish(
+            &self,
+            topic: &'static str,
+            event: Arc<dyn DynDomainEvent>,
+        ) -> Result<(), PublishError> {
+            if self.failing.load(Ordering::SeqCst)
+                || *self.rejected.lock().unwrap() == Some(*event.id())
+            {
+                return Err(PublishError::new(topic, "unavailable"));
+            }
+            self.published.lock().unwrap().push(*event.id());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn given_a_failing_publisher_when_relaying_then_messages_are_retried_in_order() {
+        let outbox = Arc::new(InMemoryOutbox::<Deposited>::new());
+        let publisher = Arc::new(FlakyPublisher::default());
+        let relay = OutboxRelay::new(outbox.clone(), publisher.clone(), "account")
+            .with_retry_delay(Duration::ZERO, Duration::ZERO);
+        let (first, second) = (deposited(), deposited());
+        let ids = vec![first.id, second.id];
+        outbox.enqueue(vec![first, second]).await.unwrap();
+
+        publisher.failing.store(true, Ordering::SeqCst);
+        assert_eq!(relay.relay_once().await.unwrap(), 0);
+        let pending = outbox.pending(10).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].attempts(), 1);
+        assert_eq!(pending[1].attempts(), 1);
+
+        publisher.failing.store(false, Ordering::SeqCst);
+        assert_eq!(relay.relay_once().await.unwrap(), 2);
+        assert!(outbox.pending(10).await.unwrap().is_empty());
+        assert_eq!(*publisher.published.lock().unwrap(), ids);
+    }
+
+    #[tokio::test]
+    async fn given_an_undeliverable_message_when_relaying_then_later_messages_are_published() {
+        let outbox = Arc::new(InMemoryOutbox::<Deposited>::new());
+        let publisher = Arc::new(FlakyPublisher::default());
+        let relay = OutboxRelay::new(outbox.clone(), publisher.clone(), "account");
+        let (poison, second) = (deposited(), deposited());
+        let (poison_id, second_id) = (poison.id, second.id);
+        *publisher.rejected.lock().unwrap() = Some(poison_id);
+        outbox.enqueue(vec![poison, second]).await.unwrap();
+
+        assert_eq!(relay.relay_once().await.unwrap(), 1);
+
+        assert_eq!(*publisher.published.lock().unwrap(), vec![second_id]);
+        // The failed message waits for its retry delay
+        assert!(outbox.pending(10).await.unwrap().is_empty());
+        assert_eq!(relay.relay.retry_delay(1), DEFAULT_RETRY_DELAY);
+        assert_eq!(relay.relay.retry_delay(3), DEFAULT_RETRY_DELAY * 4);
+        assert_eq!(relay.relay.retry_delay(40), DEFAULT_MAX_RETRY_DELAY);
+    }
+
+    #[tokio::test]
+    async fn given_a_message_failing_every_attempt_when_relaying_then_it_is_parked() {
+        let outbox = Arc::new(InMemoryOutbox::<Deposited>::new());
+        let publisher = Arc::new(FlakyPublisher::default());
+        let relay = OutboxRelay::new(outbox.clone(), publisher.clone(), "account")
+            .with_max_attempts(2)
+            .with_retry_delay(Duration::ZERO, Duration::ZERO)
+            .with_batch_size(0);
+        publisher.failing.store(true, Ordering::SeqCst);
+        outbox.enqueue(vec![deposited()]).await.unwrap();
+
+        assert_eq!(relay.relay_once().await.unwrap(), 0);
+        assert_eq!(outbox.pending(10).await.unwrap().len(), 1);
+        assert_eq!(relay.relay_once().await.unwrap(), 0);
+
+        assert!(outbox.pending(10).await.unwrap().is_empty());
+        let parked = outbox.parked(10).await.unwrap();
+        assert_eq!(parked.len(), 1);
+        assert_eq!(parked[0].attempts(), 2);
+    }
+
+    #[tokio::test]
+    async fn given_a_started_relay_when_enqueueing_then_messages_are_published_in_background() {
+        let outbox = Arc::new(InMemoryOutbox::<Deposited>::new());
+        let publisher = Arc::new(FlakyPublisher::default());
+        let relay = OutboxRelay::new(outbox.clone(), publisher.clone(), "account");
+        let handle = relay.start(Duration::from_millis(1));
+
+        outbox.enqueue(vec![deposited()]).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        relay.shutdown();
+        handle.await.unwrap();
+
+        assert_eq!(publisher.published.lock().unwrap().len(), 1);
+        assert!(outbox.pending(10).await.unwrap().is_empty());
+    }
+}
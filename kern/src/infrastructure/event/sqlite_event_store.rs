@@ -0,0 +1,374 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use rusqlite::{Connection, TransactionBehavior, params};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    application::{
+        error::{concurrency_conflict::ConcurrencyConflict, event_store_error::EventStoreError},
+        event_store::{EventStore, RecordedEvent, StreamId, validate_append},
+    },
+    building_blocks::{domain_event::DomainEvent, event_sourced::EventSourced},
+    infrastructure::persistence::sqlite::{SqliteDatabase, is_unique_violation},
+};
+
+/// The SqliteEventStore is an EventStore that appends the Domain Events as JSON to the `events`
+/// table of a SqliteDatabase. The table has a unique `(aggregate_type, aggregate_id,
+/// aggregate_version)` constraint, so concurrent writers to the same stream fail with a
+/// `ConcurrencyConflict` rather than corrupting the stream
+pub struct SqliteEventStore<A> {
+    database: SqliteDatabase,
+    _aggregate: PhantomData<fn() -> A>,
+}
+
+impl<A> SqliteEventStore<A> {
+    /// Creates a new SqliteEventStore
+    /// # Arguments
+    /// * `database` - The database the Domain Events are stored in
+    pub fn new(database: SqliteDatabase) -> Self {
+        Self {
+            database,
+            _aggregate: PhantomData,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<A> EventStore<A> for SqliteEventStore<A>
+where
+    A: EventSourced + 'static,
+    A::Event: Serialize + DeserializeOwned + Send + Sync + 'static,
+    StreamId<A>: Serialize + Send + Sync,
+{
+    async fn append(
+        &self,
+        aggregate_id: &StreamId<A>,
+        expected_version: u32,
+        events: Vec<A::Event>,
+    ) -> Result<(), EventStoreError> {
+        validate_append(aggregate_id, expected_version, &events)?;
+        let stream_id = serde_json::to_string(aggregate_id).map_err(EventStoreError::storage)?;
+        self.database
+            .run_blocking(move |connection| {
+                append_with::<A>(connection, &stream_id, expected_version, &events)
+            })
+            .await
+            .map_err(EventStoreError::storage)?
+    }
+
+    async fn read_stream(
+        &self,
+        aggregate_id: &StreamId<A>,
+        from_version: u32,
+    ) -> Result<Vec<Arc<A::Event>>, EventStoreError> {
+        let stream_id = serde_json::to_string(aggregate_id).map_err(EventStoreError::storage)?;
+        self.database
+            .run_blocking(move |connection| {
+                let mut statement = connection
+                    .prepare(
+                        "SELECT data FROM events
+                         WHERE aggregate_type = ?1 AND aggregate_id = ?2
+                         AND aggregate_version >= ?3
+                         ORDER BY aggregate_version",
+                    )
+                    .map_err(EventStoreError::storage)?;
+                let rows = statement
+                    .query_map(params![A::type_name(), stream_id, from_version], |row| {
+                        row.get::<_, String>(0)
+                    })
+                    .map_err(EventStoreError::storage)?;
+
+                rows.map(|data| {
+                    let data = data.map_err(EventStoreError::storage)?;
+                    serde_json::from_str(&data)
+                        .map(Arc::new)
+                        .map_err(EventStoreError::storage)
+                })
+                .collect()
+            })
+            .await
+            .map_err(EventStoreError::storage)?
+    }
+
+    async fn read_all(
+        &self,
+        from_position: u64,
+    ) -> Result<Vec<RecordedEvent<A::Event>>, EventStoreError> {
+        self.database
+            .run_blocking(move |connection| {
+                let mut statement = connection
+                    .prepare(
+                        "SELECT position, data FROM events
+                         WHERE aggregate_type = ?1 AND position > ?2
+                         ORDER BY position",
+                    )
+                    .map_err(EventStoreError::storage)?;
+                // SQLite positions start at 1, RecordedEvent positions start at 0
+                let rows = statement
+                    .query_map(params![A::type_name(), from_position as i64], |row| {
+                        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+                    })
+                    .map_err(EventStoreError::storage)?;
+
+                rows.map(|row| {
+                    let (position, data) = row.map_err(EventStoreError::storage)?;
+                    let event = serde_json::from_str(&data).map_err(EventStoreError::storage)?;
+                    Ok(RecordedEvent::new(position as u64 - 1, Arc::new(event)))
+                })
+                .collect()
+            })
+            .await
+            .map_err(EventStoreError::storage)?
+    }
+}
+
+/// Appends the Domain Events to the stream in a single transaction
+/// # Arguments
+/// * `connection` - The connection to the SQLite database
+/// * `stream_id` - The JSON encoded identifier of the stream
+/// * `expected_version` - The version the stream is expected to have
+/// * `events` - The Domain Events to append
+fn append_with<A>(
+    connection: &mut Connection,
+    stream_id: &str,
+    expected_version: u32,
+    events: &[A::Event],
+) -> Result<(), EventStoreError>
+where
+    A: EventSourced,
+    A::Event: Serialize,
+{
+    let transaction = connection
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(EventStoreError::storage)?;
+
+    let actual_version = stream_version::<A>(&transaction, stream_id)?;
+    if actual_version != expected_version {
+        return Err(
+            ConcurrencyConflict::new(A::error_prefix(), expected_version, actual_version).into(),
+        );
+    }
+
+    for event in events {
+        let data = serde_json::to_string(event).map_err(EventStoreError::storage)?;
+        let inserted = transaction.execute(
+            "INSERT INTO events
+                 (aggregate_type, aggregate_id, aggregate_version, event_id, occurred_at, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                A::type_name(),
+                stream_id,
+                event.aggregate_version(),
+                event.id().value().to_string(),
+                event.occurred_at().to_rfc3339(),
+                data
+            ],
+        );
+        match inserted {
+            Ok(_) => {}
+            Err(err) if is_unique_violation(&err, "events.event_id") => {
+                return Err(EventStoreError::DuplicateEvent {
+                    event_id: *event.id(),
+                });
+            }
+            Err(err) if is_unique_violation(&err, "events.aggregate_version") => {
+                // The conflict reports the persisted version, not the version of the event
+                transaction.rollback().map_err(EventStoreError::storage)?;
+                let actual_version = stream_version::<A>(connection, stream_id)?;
+                return Err(ConcurrencyConflict::new(
+                    A::error_prefix(),
+                    expected_version,
+                    actual_version,
+                )
+                .into());
+            }
+            Err(err) => return Err(EventStoreError::storage(err)),
+        }
+    }
+
+    transaction.commit().map_err(EventStoreError::storage)
+}
+
+/// Reads the version of the last Domain Event of a stream, 0 if the stream is empty
+/// # Arguments
+/// * `connection` - The connection or transaction to the SQLite database
+/// * `stream_id` - The serialized identifier of the Aggregate
+fn stream_version<A>(connection: &Connection, stream_id: &str) -> Result<u32, EventStoreError>
+where
+    A: EventSourced,
+{
+    connection
+        .query_row(
+            "SELECT COALESCE(MAX(aggregate_version), 0) FROM events
+             WHERE aggregate_type = ?1 AND aggregate_id = ?2",
+            params![A::type_name(), stream_id],
+            |row| row.get(0),
+        )
+        .map_err(EventStoreError::storage)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::building_blocks::{
+        aggregate::Aggregate,
+        entity::Entity,
+        ids::{AggregateId, EventId},
+    };
+    use chrono::{DateTime, Utc};
+    use serde::Deserialize;
+    use uuid::Uuid;
+
+    // The derive macros refer to the crate by its name
+    use crate as kern;
+
+    #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Serialize, Deserialize)]
+    struct AccountId(Uuid);
+
+    impl AggregateId for AccountId {}
+
+    #[derive(crate::Aggregate, Debug)]
+    struct Account {
+        #[entity_id]
+        id: AccountId,
+        #[field]
+        balance: i64,
+        version: u32,
+    }
+
+    #[derive(crate::DomainEvent, Debug, Serialize, Deserialize)]
+    struct Deposited {
+        id: EventId,
+        aggregate_id: AccountId,
+        aggregate_version: u32,
+        occurred_at: DateTime<Utc>,
+        amount: i64,
+    }
+
+    impl Deposited {
+        fn new(aggregate_id: AccountId, aggregate_version: u32, amount: i64) -> Self {
+            Self {
+                id: EventId::new_random_v4(),
+                aggregate_id,
+                aggregate_version,
+                occurred_at: Utc::now(),
+                amount,
+            }
+        }
+    }
+
+    impl EventSourced for Account {
+        type Event = Deposited;
+
+        fn apply(&mut self, event: &Deposited) {
+            self.balance += event.amount;
+        }
+    }
+
+    #[tokio::test]
+    async fn given_appended_events_when_reading_the_stream_then_the_aggregate_is_rehydrated() {
+        let store = SqliteEventStore::<Account>::new(SqliteDatabase::open_in_memory().unwrap());
+        let id = AccountId(Uuid::new_v4());
+
+        store
+            .append(
+                &id,
+                0,
+                vec![Deposited::new(id, 1, 10), Deposited::new(id, 2, 5)],
+            )
+            .await
+            .unwrap();
+
+        let events = store.read_stream(&id, 0).await.unwrap();
+        let account = Account {
+            id,
+            balance: 0,
+            version: 0,
+        }
+        .rehydrate(events.iter().map(|event| event.as_ref()))
+        .unwrap();
+
+        assert_eq!(*account.balance(), 15);
+        assert_eq!(account.version(), 2);
+        assert_eq!(store.read_all(1).await.unwrap()[0].position(), 1);
+    }
+
+    #[tokio::test]
+    async fn given_concurrent_writers_when_appending_then_the_stale_writer_fails() {
+        let store = SqliteEventStore::<Account>::new(SqliteDatabase::open_in_memory().unwrap());
+        let id = AccountId(Uuid::new_v4());
+        store
+            .append(&id, 0, vec![Deposited::new(id, 1, 10)])
+            .await
+            .unwrap();
+
+        let result = store.append(&id, 0, vec![Deposited::new(id, 1, 10)]).await;
+
+        assert!(matches!(
+            result,
+            Err(EventStoreError::ConcurrencyConflict(_))
+        ));
+        assert_eq!(store.read_stream(&id, 0).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn given_a_taken_version_when_inserting_then_the_conflict_reports_the_persisted_version()
+    {
+        let store = SqliteEventStore::<Account>::new(SqliteDatabase::open_in_memory().unwrap());
+        let id = AccountId(Uuid::new_v4());
+        store
+            .append(&id, 0, vec![Deposited::new(id, 1, 10)])
+            .await
+            .unwrap();
+        let stream_id = serde_json::to_string(&id).unwrap();
+
+        // Two events with the same version pass the version check and collide on insert
+        let result = store
+            .database
+            .run_blocking(move |connection| {
+                append_with::<Account>(
+                    connection,
+                    &stream_id,
+                    1,
+                    &[
+                        Deposited::new(id, 2, 10),
+                        Deposited::new(id, 3, 10),
+                        Deposited::new(id, 3, 10),
+                    ],
+                )
+            })
+            .await
+            .unwrap();
+
+        match result {
+            Err(EventStoreError::ConcurrencyConflict(conflict)) => {
+                assert_eq!(conflict.expected_version(), 1);
+                assert_eq!(conflict.actual_version(), 1);
+            }
+            other => panic!("Expected a ConcurrencyConflict, got {other:?}"),
+        }
+        assert_eq!(store.read_stream(&id, 0).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn given_a_duplicate_event_when_appending_then_the_append_is_rolled_back() {
+        let store = SqliteEventStore::<Account>::new(SqliteDatabase::open_in_memory().unwrap());
+        let id = AccountId(Uuid::new_v4());
+        let event = Deposited::new(id, 1, 10);
+        let duplicate = Deposited {
+            id: event.id,
+            aggregate_id: id,
+            aggregate_version: 2,
+            occurred_at: Utc::now(),
+            amount: 10,
+        };
+
+        let result = store.append(&id, 0, vec![event, duplicate]).await;
+
+        assert!(matches!(
+            result,
+            Err(EventStoreError::DuplicateEvent { .. })
+        ));
+        assert!(store.read_all(0).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,223 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, params};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    application::{
+        error::outbox_error::OutboxError,
+        outbox::{Outbox, OutboxMessage},
+    },
+    infrastructure::persistence::sqlite::SqliteDatabase,
+};
+
+/// The SqliteOutbox is an Outbox that stores its messages as JSON in the `outbox` table of a
+/// SqliteDatabase. Use `SqliteRepository::save_with_outbox` to enqueue the Domain Events in the
+/// same transaction that saves their Aggregate. The retry time of a failed message is stored as
+/// milliseconds since the Unix epoch, and a parked message keeps its row with a `parked_at` time
+pub struct SqliteOutbox<E> {
+    database: SqliteDatabase,
+    topic: &'static str,
+    _event: PhantomData<fn() -> E>,
+}
+
+impl<E> SqliteOutbox<E> {
+    /// Creates a new SqliteOutbox
+    /// # Arguments
+    /// * `database` - The database the messages are stored in
+    /// * `topic` - The topic the messages are published to, which separates the messages of
+    ///   several SqliteOutboxes sharing the same database
+    pub fn new(database: SqliteDatabase, topic: &'static str) -> Self {
+        Self {
+            database,
+            topic,
+            _event: PhantomData,
+        }
+    }
+
+    /// The database the messages are stored in
+    pub(crate) fn database(&self) -> &SqliteDatabase {
+        &self.database
+    }
+
+    /// The topic the messages are published to
+    pub(crate) fn topic(&self) -> &'static str {
+        self.topic
+    }
+}
+
+/// Enqueues the Domain Events using the connection, so the caller controls the transaction
+/// # Arguments
+/// * `connection` - The connection or transaction to the SQLite database
+/// * `topic` - The topic the messages are published to
+/// * `events` - The Domain Events to enqueue
+pub(crate) fn enqueue_with<E>(
+    connection: &Connection,
+    topic: &str,
+    events: &[E],
+) -> Result<(), OutboxError>
+where
+    E: Serialize,
+{
+    let mut statement = connection
+        .prepare_cached("INSERT INTO outbox (topic, data) VALUES (?1, ?2)")
+        .map_err(OutboxError::storage)?;
+    for event in events {
+        let data = serde_json::to_string(event).map_err(OutboxError::storage)?;
+        statement
+            .execute(params![topic, data])
+            .map_err(OutboxError::storage)?;
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl<E> Outbox<E> for SqliteOutbox<E>
+where
+    E: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    async fn enqueue(&self, events: Vec<E>) -> Result<(), OutboxError> {
+        let topic = self.topic;
+        self.database
+            .run_blocking(move |connection| {
+                let transaction = connection.transaction().map_err(OutboxError::storage)?;
+                enqueue_with(&transaction, topic, &events)?;
+                transaction.commit().map_err(OutboxError::storage)
+            })
+            .await
+            .map_err(OutboxError::storage)?
+    }
+
+    async fn pending(&self, limit: usize) -> Result<Vec<OutboxMessage<E>>, OutboxError> {
+        let topic = self.topic;
+        let now = Utc::now().timestamp_millis();
+        self.database
+            .run_blocking(move |connection| {
+                select_messages(
+                    connection,
+                    "SELECT id, attempts, retry_at, data FROM outbox
+                     WHERE topic = ?1 AND dispatched_at IS NULL AND parked_at IS NULL
+                     AND (retry_at IS NULL OR retry_at <= ?3)
+                     ORDER BY id LIMIT ?2",
+                    params![topic, limit as i64, now],
+                )
+            })
+            .await
+            .map_err(OutboxError::storage)?
+    }
+
+    async fn mark_dispatched(&self, id: u64) -> Result<(), OutboxError> {
+        let topic = self.topic;
+        self.database
+            .run_blocking(move |connection| {
+                let rows = connection
+                    .execute(
+                        "UPDATE outbox SET dispatched_at = ?3
+                         WHERE id = ?1 AND topic = ?2 AND dispatched_at IS NULL
+                         AND parked_at IS NULL",
+                        params![id as i64, topic, chrono::Utc::now().to_rfc3339()],
+                    )
+                    .map_err(OutboxError::storage)?;
+                if rows == 0 {
+                    return Err(OutboxError::UnknownMessage { id });
+                }
+                Ok(())
+            })
+            .await
+            .map_err(OutboxError::storage)?
+    }
+
+    async fn mark_failed(&self, id: u64, retry_at: DateTime<Utc>) -> Result<(), OutboxError> {
+        let topic = self.topic;
+        self.database
+            .run_blocking(move |connection| {
+                let rows = connection
+                    .execute(
+                        "UPDATE outbox SET attempts = attempts + 1, retry_at = ?3
+                         WHERE id = ?1 AND topic = ?2 AND dispatched_at IS NULL
+                         AND parked_at IS NULL",
+                        params![id as i64, topic, retry_at.timestamp_millis()],
+                    )
+                    .map_err(OutboxError::storage)?;
+                if rows == 0 {
+                    return Err(OutboxError::UnknownMessage { id });
+                }
+                Ok(())
+            })
+            .await
+            .map_err(OutboxError::storage)?
+    }
+
+    async fn mark_parked(&self, id: u64) -> Result<(), OutboxError> {
+        let topic = self.topic;
+        self.database
+            .run_blocking(move |connection| {
+                let rows = connection
+                    .execute(
+                        "UPDATE outbox SET attempts = attempts + 1, retry_at = NULL, parked_at = ?3
+                         WHERE id = ?1 AND topic = ?2 AND dispatched_at IS NULL
+                         AND parked_at IS NULL",
+                        params![id as i64, topic, Utc::now().to_rfc3339()],
+                    )
+                    .map_err(OutboxError::storage)?;
+                if rows == 0 {
+                    return Err(OutboxError::UnknownMessage { id });
+                }
+                Ok(())
+            })
+            .await
+            .map_err(OutboxError::storage)?
+    }
+
+    async fn parked(&self, limit: usize) -> Result<Vec<OutboxMessage<E>>, OutboxError> {
+        let topic = self.topic;
+        self.database
+            .run_blocking(move |connection| {
+                select_messages(
+                    connection,
+                    "SELECT id, attempts, retry_at, data FROM outbox
+                     WHERE topic = ?1 AND dispatched_at IS NULL AND parked_at IS NOT NULL
+                     ORDER BY id LIMIT ?2",
+                    params![topic, limit as i64],
+                )
+            })
+            .await
+            .map_err(OutboxError::storage)?
+    }
+}
+
+/// Selects the messages of the query, which returns the `id`, `attempts`, `retry_at` and `data`
+/// columns
+/// # Arguments
+/// * `connection` - The connection to the SQLite database
+/// * `query` - The query of the messages
+/// * `params` - The parameters of the query
+fn select_messages<E>(
+    connection: &Connection,
+    query: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<OutboxMessage<E>>, OutboxError>
+where
+    E: DeserializeOwned,
+{
+    let mut statement = connection.prepare(query).map_err(OutboxError::storage)?;
+    let rows = statement
+        .query_map(params, |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, u32>(1)?,
+                row.get::<_, Option<i64>>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })
+        .map_err(OutboxError::storage)?;
+
+    rows.map(|row| {
+        let (id, attempts, retry_at, data) = row.map_err(OutboxError::storage)?;
+        let event = serde_json::from_str(&data).map_err(OutboxError::storage)?;
+        Ok(OutboxMessage::new(id as u64, attempts, Arc::new(event))
+            .with_retry_at(retry_at.and_then(DateTime::from_timestamp_millis)))
+    })
+    .collect()
+}
@@ -0,0 +1,4 @@
+#[cfg(feature = "jwt")]
+pub mod jwt;
+#[cfg(feature = "axum")]
+pub mod request_context;
@@ -0,0 +1,495 @@
+use std::{collections::HashSet, marker::PhantomData, path::Path, str::FromStr};
+
+use axum::{
+    http::{header, request::Parts},
+    response::{IntoResponse, Response},
+};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, jwk::JwkSet};
+use serde_json::Value;
+
+use crate::{
+    application::{error::unauthorized_error::UnauthorizedError, ids::AuthorizedParty, role::Role},
+    building_blocks::ids::UserId,
+    infrastructure::http::request_context::{Claims, ClaimsSource},
+};
+
+/// The ClaimMapping names the claims of a JWT the identity of the user is read from. Nested claims
+/// are addressed with a dot separated path, e.g. `realm_access.roles`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimMapping {
+    /// The claim of the identifier of the user
+    user_id: String,
+    /// The claim of the identifier of the client that requested the token
+    authorized_party: String,
+    /// The claims of the roles of the user, which are merged
+    roles: Vec<String>,
+}
+
+impl Default for ClaimMapping {
+    fn default() -> Self {
+        Self {
+            user_id: "sub".to_string(),
+            authorized_party: "azp".to_string(),
+            roles: vec!["roles".to_string(), "realm_access.roles".to_string()],
+        }
+    }
+}
+
+impl ClaimMapping {
+    /// Sets the claim of the identifier of the user, `sub` by default
+    /// # Arguments
+    /// * `claim` - The path of the claim
+    pub fn with_user_id_claim<S>(mut self, claim: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.user_id = claim.into();
+        self
+    }
+
+    /// Sets the claim of the identifier of the client, `azp` by default
+    /// # Arguments
+    /// * `claim` - The path of the claim
+    pub fn with_authorized_party_claim<S>(mut self, claim: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.authorized_party = claim.into();
+        self
+    }
+
+    /// Sets the claims of the roles of the user, `roles` and `realm_access.roles` by default
+    /// # Arguments
+    /// * `claims` - The paths of the claims
+    pub fn with_roles_claims<I, S>(mut self, claims: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.roles = claims.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// A JwksError is an error that is returned when a JSON Web Key Set cannot be loaded
+#[derive(Debug)]
+pub enum JwksError {
+    /// The file of the key set cannot be read
+    Read(std::io::Error),
+    /// The key set or one of its keys is invalid
+    Invalid(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl std::fmt::Display for JwksError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(error) => write!(f, "Cannot read the JSON Web Key Set: {error}"),
+            Self::Invalid(error) => write!(f, "Invalid JSON Web Key Set: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for JwksError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Read(error) => Some(error),
+            Self::Invalid(error) => Some(error.as_ref()),
+        }
+    }
+}
+
+/// A key of a JSON Web Key Set
+struct Jwk {
+    /// The identifier of the key
+    key_id: Option<String>,
+    /// The algorithm the key is restricted to
+    algorithm: Option<Algorithm>,
+    key: DecodingKey,
+}
+
+/// The keys the signature of a JWT is verified with
+enum VerificationKeys {
+    Hmac(DecodingKey),
+    Jwks(Vec<Jwk>),
+}
+
+/// The JwtVerifier verifies bearer tokens against locally configured keys and maps their claims
+/// to the identity of the user. As a ClaimsSource it provides the identity for the RequestContext
+/// extractor.
+///
+/// Tokens are verified with an HMAC secret or with the keys of a JSON Web Key Set. A key of a key
+/// set is selected by the `kid` of the token, and a token is only accepted with the algorithm of
+/// the key, so a public key can never be used as an HMAC secret. The expiry of a token is always
+/// validated; the issuer and the audience are validated when they are configured
+///
+/// ```
+/// use kern::infrastructure::http::jwt::{ClaimMapping, JwtVerifier};
+///
+/// let verifier = JwtVerifier::<String>::hmac(b"secret")
+///     .with_issuer(["https://auth.example.com"])
+///     .with_claim_mapping(ClaimMapping::default().with_roles_claims(["groups"]));
+///
+/// assert!(verifier.verify("not-a-token").is_err());
+/// ```
+pub struct JwtVerifier<T> {
+    keys: VerificationKeys,
+    mapping: ClaimMapping,
+    issuer: Option<Vec<String>>,
+    audience: Option<Vec<String>>,
+    leeway: u64,
+    _user_id: PhantomData<fn() -> T>,
+}
+
+impl<T> JwtVerifier<T> {
+    /// Creates a JwtVerifier that verifies tokens signed with HS256, HS384 or HS512
+    /// # Arguments
+    /// * `secret` - The HMAC secret
+    pub fn hmac(secret: &[u8]) -> Self {
+        Self::new(VerificationKeys::Hmac(DecodingKey::from_secret(secret)))
+    }
+
+    /// Creates a JwtVerifier that verifies tokens with the keys of a JSON Web Key Set
+    /// # Arguments
+    /// * `jwks` - The JSON of the key set
+    pub fn from_jwks(jwks: &str) -> Result<Self, JwksError> {
+        let set: JwkSet =
+            serde_json::from_str(jwks).map_err(|err| JwksError::Invalid(err.into()))?;
+        let keys = set
+            .keys
+            .iter()
+            .map(|jwk| {
+                let algorithm = jwk
+                    .common
+                    .key_algorithm
+                    .map(|algorithm| Algorithm::from_str(&algorithm.to_string()))
+                    .transpose()
+                    .map_err(|err| JwksError::Invalid(err.into()))?;
+                Ok(Jwk {
+                    key_id: jwk.common.key_id.clone(),
+                    algorithm,
+                    key: DecodingKey::from_jwk(jwk)
+                        .map_err(|err| JwksError::Invalid(err.into()))?,
+                })
+            })
+            .collect::<Result<_, JwksError>>()?;
+        Ok(Self::new(VerificationKeys::Jwks(keys)))
+    }
+
+    /// Creates a JwtVerifier that verifies tokens with the keys of a JSON Web Key Set file
+    /// # Arguments
+    /// * `path` - The path of the key set file
+    pub fn from_jwks_file<P>(path: P) -> Result<Self, JwksError>
+    where
+        P: AsRef<Path>,
+    {
+        let jwks = std::fs::read_to_string(path).map_err(JwksError::Read)?;
+        Self::from_jwks(&jwks)
+    }
+
+    fn new(keys: VerificationKeys) -> Self {
+        Self {
+            keys,
+            mapping: ClaimMapping::default(),
+            issuer: None,
+            audience: None,
+            leeway: 60,
+            _user_id: PhantomData,
+        }
+    }
+
+    /// Sets the claims the identity of the user is read from
+    /// # Arguments
+    /// * `mapping` - The ClaimMapping
+    pub fn with_claim_mapping(mut self, mapping: ClaimMapping) -> Self {
+        self.mapping = mapping;
+        self
+    }
+
+    /// Only accepts tokens that were issued by one of the issuers
+    /// # Arguments
+    /// * `issuers` - The accepted values of the `iss` claim
+    pub fn with_issuer<I, S>(mut self, issuers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.issuer = Some(issuers.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Only accepts tokens that were issued for one of the audiences
+    /// # Arguments
+    /// * `audiences` - The accepted values of the `aud` claim
+    pub fn with_audience<I, S>(mut self, audiences: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.audience = Some(audiences.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the clock skew that is tolerated when the expiry of a token is validated, 60 seconds
+    /// by default
+    /// # Arguments
+    /// * `seconds` - The tolerated clock skew in seconds
+    pub fn with_leeway(mut self, seconds: u64) -> Self {
+        self.leeway = seconds;
+        self
+    }
+
+    /// Creates the Validation for the algorithms of a key
+    /// # Arguments
+    /// * `algorithms` - The algorithms the key can be used with
+    fn validation(&self, algorithms: Vec<Algorithm>) -> Validation {
+        let mut validation = Validation::new(algorithms[0]);
+        validation.algorithms = algorithms;
+        validation.leeway = self.leeway;
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(issuer);
+        }
+        match &self.audience {
+            Some(audience) => validation.set_audience(audience),
+            None => validation.validate_aud = false,
+        }
+        validation
+    }
+
+    /// Verifies the signature of the token and returns its claims
+    /// # Arguments
+    /// * `token` - The encoded token
+    fn decode(&self, token: &str) -> Result<Value, UnauthorizedError> {
+        let header = jsonwebtoken::decode_header(token).map_err(|_| invalid_token())?;
+        let (key, algorithms) = match &self.keys {
+            VerificationKeys::Hmac(key) => (
+                key,
+                vec![Algorithm::HS256, Algorithm::HS384, Algorithm::HS512],
+            ),
+            VerificationKeys::Jwks(keys) => {
+                let jwk = match &header.kid {
+                    Some(kid) => keys.iter().find(|jwk| jwk.key_id.as_ref() == Some(kid)),
+                    None if keys.len() == 1 => keys.first(),
+                    None => None,
+                }
+                .ok_or_else(invalid_token)?;
+                (&jwk.key, vec![jwk.algorithm.unwrap_or(header.alg)])
+            }
+        };
+
+        jsonwebtoken::decode::<Value>(token, key, &self.validation(algorithms))
+            .map(|data| data.claims)
+            .map_err(|_| invalid_token())
+    }
+
+    /// Verifies the token and maps its claims to the identity of the user
+    /// # Arguments
+    /// * `token` - The encoded token
+    pub fn verify(&self, token: &str) -> Result<Claims<UserId<T>>, UnauthorizedError>
+    where
+        T: FromStr,
+    {
+        let claims = self.decode(token)?;
+
+        let user_id = claim(&claims, &self.mapping.user_id)
+            .and_then(Value::as_str)
+            .and_then(|user_id| user_id.parse().ok())
+            .ok_or_else(|| invalid_claim(&self.mapping.user_id))?;
+        let authorized_party = claim(&claims, &self.mapping.authorized_party)
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid_claim(&self.mapping.authorized_party))?;
+        let roles: HashSet<Role> = self
+            .mapping
+            .roles
+            .iter()
+            .filter_map(|path| claim(&claims, path))
+            .flat_map(|roles| match roles {
+                Value::Array(roles) => roles.iter().filter_map(Value::as_str).collect(),
+                Value::String(role) => vec![role.as_str()],
+                _ => Vec::new(),
+            })
+            .map(|role| Role::new(role.to_string()))
+            .collect();
+
+        Ok(Claims::new(
+            UserId::new(user_id),
+            AuthorizedParty::new(authorized_party.to_string()),
+            roles,
+        ))
+    }
+}
+
+/// Looks up a claim by its dot separated path
+/// # Arguments
+/// * `claims` - The claims of the token
+/// * `path` - The path of the claim
+fn claim<'a>(claims: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(claims, |value, segment| value.get(segment))
+}
+
+crate::declare_errors! {
+    /// The request has no bearer token
+    pub MISSING_TOKEN = "error.authentication.missing-token" => "The request does not contain a bearer token";
+    /// The bearer token cannot be verified
+    pub INVALID_TOKEN = "error.authentication.invalid-token" => "The bearer token is invalid or expired";
+    /// The bearer token lacks the `claim` parameter or the claim has an invalid value
+    pub INVALID_CLAIMS = "error.authentication.invalid-claims" => "The bearer token does not contain valid claims";
+}
+
+fn missing_token() -> UnauthorizedError {
+    UnauthorizedError::new(MISSING_TOKEN)
+}
+
+fn invalid_token() -> UnauthorizedError {
+    UnauthorizedError::new(INVALID_TOKEN)
+}
+
+fn invalid_claim(claim: &str) -> UnauthorizedError {
+    UnauthorizedError::new(INVALID_CLAIMS.with_param("claim", claim))
+}
+
+#[async_trait::async_trait]
+impl<T> ClaimsSource<UserId<T>> for JwtVerifier<T>
+where
+    T: FromStr + Send + 'static,
+{
+    async fn claims(&self, parts: &Parts) -> Result<Claims<UserId<T>>, Response> {
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| {
+                value
+                    .strip_prefix("Bearer ")
+                    .or_else(|| value.strip_prefix("bearer "))
+            })
+            .ok_or_else(|| missing_token().into_response())?;
+        self.verify(token.trim())
+            .map_err(IntoResponse::into_response)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use axum::http::{Request, StatusCode};
+    use jsonwebtoken::{EncodingKey, Header};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn expires_at() -> i64 {
+        chrono::Utc::now().timestamp() + 300
+    }
+
+    fn sign(header: Header, secret: &[u8], claims: Value) -> String {
+        jsonwebtoken::encode(&header, &claims, &EncodingKey::from_secret(secret)).unwrap()
+    }
+
+    #[test]
+    fn given_a_valid_hmac_token_when_verifying_then_the_claims_are_mapped() {
+        let user_id = Uuid::new_v4();
+        let token = sign(
+            Header::new(Algorithm::HS384),
+            b"secret",
+            json!({
+                "sub": user_id.to_string(),
+                "azp": "web-client",
+                "exp": expires_at(),
+                "roles": ["admin"],
+                "realm_access": { "roles": ["owner"] },
+            }),
+        );
+
+        let claims = JwtVerifier::<Uuid>::hmac(b"secret").verify(&token).unwrap();
+
+        assert_eq!(*claims.user_id(), UserId::new(user_id));
+        assert_eq!(claims.authorized_party().value(), "web-client");
+        assert_eq!(claims.roles().len(), 2);
+        assert!(claims.roles().contains("owner"));
+    }
+
+    #[test]
+    fn given_invalid_tokens_when_verifying_then_they_are_rejected() {
+        let verifier = JwtVerifier::<String>::hmac(b"secret").with_issuer(["kern"]);
+        let claims = json!({ "sub": "user", "azp": "cli", "iss": "kern", "exp": expires_at() });
+
+        assert!(
+            verifier
+                .verify(&sign(Header::default(), b"secret", claims.clone()))
+                .is_ok()
+        );
+        let wrong_secret = verifier.verify(&sign(Header::default(), b"other", claims.clone()));
+        let expired = verifier.verify(&sign(
+            Header::default(),
+            b"secret",
+            json!({ "sub": "user", "azp": "cli", "iss": "kern", "exp": 1 }),
+        ));
+        let missing_claim = verifier.verify(&sign(
+            Header::default(),
+            b"secret",
+            json!({ "sub": "user", "iss": "kern", "exp": expires_at() }),
+        ));
+
+        assert_eq!(wrong_secret, Err(invalid_token()));
+        assert_eq!(expired, Err(invalid_token()));
+        let missing_claim = missing_claim.unwrap_err();
+        assert_eq!(missing_claim.error_detail().key(), INVALID_CLAIMS.key());
+        assert_eq!(missing_claim.error_detail().params()["claim"], "azp");
+    }
+
+    #[test]
+    fn given_a_jwks_file_when_verifying_then_the_key_is_selected_by_kid() {
+        let path = std::env::temp_dir().join(format!("kern-jwks-{}.json", Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            json!({
+                "keys": [
+                    { "kty": "oct", "kid": "first", "alg": "HS256", "k": "Zmlyc3Q" },
+                    { "kty": "oct", "kid": "second", "alg": "HS256", "k": "c2Vjb25k" },
+                ]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let verifier = JwtVerifier::<String>::from_jwks_file(&path)
+            .unwrap()
+            .with_claim_mapping(
+                ClaimMapping::default()
+                    .with_authorized_party_claim("client.id")
+                    .with_roles_claims(["groups"]),
+            );
+        std::fs::remove_file(&path).unwrap();
+
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some("second".to_string());
+        let claims = json!({
+            "sub": "user",
+            "client": { "id": "cli" },
+            "groups": "auditor",
+            "exp": expires_at(),
+        });
+
+        let verified = verifier
+            .verify(&sign(header.clone(), b"second", claims.clone()))
+            .unwrap();
+        assert!(verified.roles().contains("auditor"));
+        assert_eq!(
+            verifier.verify(&sign(header, b"first", claims)),
+            Err(invalid_token())
+        );
+    }
+
+    #[tokio::test]
+    async fn given_no_bearer_token_when_reading_claims_then_the_request_is_unauthorized() {
+        let (parts, _) = Request::builder().body(()).unwrap().into_parts();
+
+        let response = JwtVerifier::<String>::hmac(b"secret")
+            .claims(&parts)
+            .await
+            .unwrap_err();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+    }
+}
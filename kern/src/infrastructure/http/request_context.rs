@@ -0,0 +1,256 @@
+use std::{collections::HashSet, sync::Arc};
+
+use axum::{
+    Json,
+    extract::{FromRef, FromRequestParts},
+    http::{StatusCode, request::Parts},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        environment::Environment,
+        ids::{AuthorizedParty, RequestId},
+        request_context::RequestContext,
+        role::Role,
+    },
+    infrastructure::error::axum_extensions::StatusCodeError,
+};
+
+/// The header the RequestId is read from
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+crate::declare_errors! {
+    /// The `X-Request-Id` header of the request is not a UUID
+    pub INVALID_REQUEST_ID = "error.request.invalid-request-id" => "The X-Request-Id header must be a UUID";
+}
+
+/// The Claims are the identity of the user that issued a request
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claims<U, P = AuthorizedParty> {
+    /// The identifier of the user
+    user_id: U,
+    /// The identifier of the client that issued the request
+    authorized_party: P,
+    /// The roles of the user
+    roles: HashSet<Role>,
+}
+
+impl<U, P> Claims<U, P> {
+    /// Creates new Claims
+    /// # Arguments
+    /// * `user_id` - The identifier of the user
+    /// * `authorized_party` - The identifier of the client that issued the request
+    /// * `roles` - The roles of the user
+    pub fn new(user_id: U, authorized_party: P, roles: HashSet<Role>) -> Self {
+        Self {
+            user_id,
+            authorized_party,
+            roles,
+        }
+    }
+
+    /// The identifier of the user
+    pub fn user_id(&self) -> &U {
+        &self.user_id
+    }
+
+    /// The identifier of the client that issued the request
+    pub fn authorized_party(&self) -> &P {
+        &self.authorized_party
+    }
+
+    /// The roles of the user
+    pub fn roles(&self) -> &HashSet<Role> {
+        &self.roles
+    }
+}
+
+/// A ClaimsSource reads the Claims of the user from an HTTP request, e.g. from a verified bearer
+/// token or from headers set by a trusted gateway
+#[async_trait::async_trait]
+pub trait ClaimsSource<U, P = AuthorizedParty>: Send + Sync {
+    /// Returns the Claims of the user, or the response the request is rejected with
+    /// # Arguments
+    /// * `parts` - The head of the HTTP request
+    async fn claims(&self, parts: &Parts) -> Result<Claims<U, P>, Response>;
+}
+
+/// The RequestContextState is the part of the application state the RequestContext extractor
+/// needs. The application state must implement `FromRef` for it
+pub struct RequestContextState<U, P = AuthorizedParty> {
+    /// The environment the application runs in
+    environment: Environment,
+    /// The source of the Claims of the user
+    claims_source: Arc<dyn ClaimsSource<U, P>>,
+}
+
+impl<U, P> Clone for RequestContextState<U, P> {
+    fn clone(&self) -> Self {
+        Self {
+            environment: self.environment,
+            claims_source: self.claims_source.clone(),
+        }
+    }
+}
+
+impl<U, P> RequestContextState<U, P> {
+    /// Creates a new RequestContextState
+    /// # Arguments
+    /// * `environment` - The environment the application runs in
+    /// * `claims_source` - The source of the Claims of the user
+    pub fn new(environment: Environment, claims_source: Arc<dyn ClaimsSource<U, P>>) -> Self {
+        Self {
+            environment,
+            claims_source,
+        }
+    }
+
+    /// The environment the application runs in
+    pub fn environment(&self) -> &Environment {
+        &self.environment
+    }
+}
+
+/// Reads the RequestId from the `X-Request-Id` header, or generates a time-ordered RequestId if
+/// the header is missing
+/// # Arguments
+/// * `parts` - The head of the HTTP request
+fn request_id(parts: &Parts) -> Result<RequestId, StatusCodeError> {
+    let Some(header) = parts.headers.get(REQUEST_ID_HEADER) else {
+        return Ok(RequestId::new_random_v7());
+    };
+    header
+        .to_str()
+        .ok()
+        .and_then(|value| Uuid::parse_str(value.trim()).ok())
+        .map(RequestId::new)
+        .ok_or_else(|| StatusCodeError::from(&INVALID_REQUEST_ID))
+}
+
+/// Extracts the RequestContext of an HTTP request. The RequestId is read from the `X-Request-Id`
+/// header or generated, the request is stamped with the current time, the Environment is taken
+/// from the RequestContextState and the identity of the user from its ClaimsSource
+impl<S, U, P> FromRequestParts<S> for RequestContext<U, P>
+where
+    S: Send + Sync,
+    RequestContextState<U, P>: FromRef<S>,
+    U: Send + 'static,
+    P: Send + 'static,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let issued_at = Utc::now();
+        let request_id = request_id(parts)
+            .map_err(|err| (StatusCode::BAD_REQUEST, Json(err)).into_response())?;
+        let state = RequestContextState::<U, P>::from_ref(state);
+        let claims = state.claims_source.claims(parts).await?;
+
+        Ok(RequestContext {
+            request_id,
+            authorized_party: claims.authorized_party,
+            environment: state.environment,
+            issued_at,
+            user_id: claims.user_id,
+            roles: claims.roles,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use axum::http::Request;
+
+    use super::*;
+    use crate::building_blocks::ids::UserId;
+
+    /// Trusts the identity headers set by a gateway
+    struct GatewayHeaders;
+
+    #[async_trait::async_trait]
+    impl ClaimsSource<UserId<Uuid>> for GatewayHeaders {
+        async fn claims(&self, parts: &Parts) -> Result<Claims<UserId<Uuid>>, Response> {
+            let user_id = parts
+                .headers
+                .get("x-user-id")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| Uuid::parse_str(value).ok())
+                .ok_or_else(|| StatusCode::UNAUTHORIZED.into_response())?;
+            Ok(Claims::new(
+                UserId::new(user_id),
+                AuthorizedParty::new("gateway".to_string()),
+                HashSet::from([Role::new("member".to_string())]),
+            ))
+        }
+    }
+
+    #[derive(Clone)]
+    struct AppState {
+        context: RequestContextState<UserId<Uuid>>,
+    }
+
+    impl FromRef<AppState> for RequestContextState<UserId<Uuid>> {
+        fn from_ref(state: &AppState) -> Self {
+            state.context.clone()
+        }
+    }
+
+    fn state() -> AppState {
+        AppState {
+            context: RequestContextState::new(Environment::Staging, Arc::new(GatewayHeaders)),
+        }
+    }
+
+    async fn extract(
+        request: axum::http::request::Builder,
+    ) -> Result<RequestContext<UserId<Uuid>>, Response> {
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+        RequestContext::from_request_parts(&mut parts, &state()).await
+    }
+
+    #[tokio::test]
+    async fn given_identity_headers_when_extracting_then_the_context_is_built() {
+        let request_id = Uuid::now_v7();
+        let user_id = Uuid::new_v4();
+
+        let context = extract(
+            Request::builder()
+                .header("X-Request-Id", request_id.to_string())
+                .header("x-user-id", user_id.to_string()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(context.request_id, RequestId::new(request_id));
+        assert_eq!(context.user_id, UserId::new(user_id));
+        assert_eq!(context.environment, Environment::Staging);
+        assert!(context.roles.contains("member"));
+    }
+
+    #[tokio::test]
+    async fn given_no_request_id_when_extracting_then_a_v7_request_id_is_generated() {
+        let context = extract(Request::builder().header("x-user-id", Uuid::new_v4().to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(context.request_id.value().get_version_num(), 7);
+    }
+
+    #[tokio::test]
+    async fn given_invalid_headers_when_extracting_then_the_request_is_rejected() {
+        let invalid_request_id = extract(
+            Request::builder()
+                .header("x-request-id", "not-a-uuid")
+                .header("x-user-id", Uuid::new_v4().to_string()),
+        )
+        .await
+        .unwrap_err();
+        let missing_claims = extract(Request::builder()).await.unwrap_err();
+
+        assert_eq!(invalid_request_id.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(missing_claims.status(), StatusCode::UNAUTHORIZED);
+    }
+}
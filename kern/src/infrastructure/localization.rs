@@ -0,0 +1,3 @@
+#[cfg(feature = "axum")]
+pub mod accept_language;
+pub mod message_catalog;
@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::Response,
+};
+
+use crate::infrastructure::{
+    error::axum_extensions::{ResponseErrorDetails, with_body},
+    localization::message_catalog::{MessageCatalog, accepted_locales},
+};
+
+/// Localizes the messages of the error responses of the application into the locales of the
+/// `Accept-Language` header of the request.
+///
+/// To render localized problem details the middleware must run inside `render_errors`, i.e. be
+/// layered before it
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use axum::{Router, middleware, routing::get};
+/// use kern::building_blocks::error::{domain_error::DomainError, error_detail::ErrorDetail};
+/// use kern::infrastructure::error::problem_details::{ResponseFormat, render_errors};
+/// use kern::infrastructure::localization::{
+///     accept_language::localize_errors, message_catalog::MessageCatalog,
+/// };
+///
+/// async fn rename_tenant() -> Result<(), DomainError> {
+///     Err(DomainError::single(ErrorDetail::new("error.tenant.invalid-name", "Invalid name")))
+/// }
+///
+/// let mut catalog = MessageCatalog::new("en").unwrap();
+/// catalog
+///     .add_fluent("de", "error_tenant_invalid-name = Ungültiger Name")
+///     .unwrap();
+///
+/// let app: Router = Router::new()
+///     .route("/tenants", get(rename_tenant))
+///     .layer(middleware::from_fn_with_state(Arc::new(catalog), localize_errors))
+///     .layer(middleware::from_fn_with_state(ResponseFormat::StatusCode, render_errors));
+/// ```
+pub async fn localize_errors(
+    State(catalog): State<Arc<MessageCatalog>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let locales = accepted_locales(
+        &request
+            .headers()
+            .get_all(header::ACCEPT_LANGUAGE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(","),
+    );
+
+    let response = next.run(request).await;
+    let Some(ResponseErrorDetails { details, multiple }) =
+        response.extensions().get::<ResponseErrorDetails>().cloned()
+    else {
+        return response;
+    };
+
+    let (parts, _) = response.into_parts();
+    let localized = ResponseErrorDetails {
+        details: details
+            .iter()
+            .map(|detail| catalog.localize(detail, &locales))
+            .collect(),
+        multiple,
+    };
+    let status = parts.status;
+    with_body(parts, localized.render(status))
+}
+
+#[cfg(test)]
+mod test {
+    use axum::{Router, body::Body, middleware, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{
+        application::error::forbidden_error::ForbiddenError,
+        infrastructure::error::{
+            axum_extensions::StatusCodeError,
+            problem_details::{ProblemDetails, ResponseFormat, render_errors},
+        },
+    };
+
+    async fn forbidden() -> Result<(), ForbiddenError> {
+        Err(ForbiddenError::for_use_case("delete-tenant"))
+    }
+
+    fn app() -> Router {
+        let mut catalog = MessageCatalog::new("en").unwrap();
+        catalog
+            .add_fluent(
+                "de",
+                "error_delete-tenant_forbidden = Sie dürfen keine Mandanten löschen",
+            )
+            .unwrap();
+        Router::new()
+            .route("/forbidden", get(forbidden))
+            .layer(middleware::from_fn_with_state(
+                Arc::new(catalog),
+                localize_errors,
+            ))
+            .layer(middleware::from_fn_with_state(
+                ResponseFormat::StatusCode,
+                render_errors,
+            ))
+    }
+
+    async fn body(response: Response) -> Vec<u8> {
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec()
+    }
+
+    #[tokio::test]
+    async fn given_an_accept_language_header_when_failing_then_the_message_is_localized() {
+        let response = app()
+            .oneshot(
+                Request::builder()
+                    .uri("/forbidden")
+                    .header(header::ACCEPT_LANGUAGE, "de-DE, en;q=0.8")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), axum::http::StatusCode::FORBIDDEN);
+        let error: StatusCodeError = serde_json::from_slice(&body(response).await).unwrap();
+        assert_eq!(error.description(), "Sie dürfen keine Mandanten löschen");
+
+        let response = app()
+            .oneshot(
+                Request::builder()
+                    .uri("/forbidden")
+                    .header(header::ACCEPT_LANGUAGE, "de")
+                    .header(header::ACCEPT, "application/problem+json")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let problem: ProblemDetails = serde_json::from_slice(&body(response).await).unwrap();
+        assert_eq!(problem.title, "Sie dürfen keine Mandanten löschen");
+    }
+}
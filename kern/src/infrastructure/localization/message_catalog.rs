@@ -0,0 +1,476 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue, concurrent::FluentBundle};
+pub use unic_langid::LanguageIdentifier;
+
+use crate::building_blocks::error::error_detail::ErrorDetail;
+
+/// A CatalogError is an error that is returned when the translations of a MessageCatalog cannot
+/// be loaded
+#[derive(Debug)]
+pub enum CatalogError {
+    /// A file of translations cannot be read
+    Read(std::io::Error),
+    /// A locale is not a valid language identifier
+    InvalidLocale(String),
+    /// The translations of a locale are invalid
+    Invalid {
+        /// The locale of the translations
+        locale: String,
+        /// The reason the translations are invalid
+        reason: String,
+    },
+}
+
+impl std::fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(error) => write!(f, "Cannot read the translations: {error}"),
+            Self::InvalidLocale(locale) => write!(f, "Invalid locale '{locale}'"),
+            Self::Invalid { locale, reason } => {
+                write!(f, "Invalid translations for '{locale}': {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Read(error) => Some(error),
+            Self::InvalidLocale(_) | Self::Invalid { .. } => None,
+        }
+    }
+}
+
+/// The MessageCatalog resolves the key and parameters of an ErrorDetail into a localized message.
+///
+/// Translations are Fluent messages. Fluent identifiers cannot contain dots, so the dots of a key
+/// are written as underscores, e.g. `error_tenant_invalid-name` for `error.tenant.invalid-name`.
+/// Only keys that start with a letter and consist of letters, digits, dots and hyphens can be
+/// translated, so no two keys share an identifier; other keys keep their message. The keys that
+/// are generated from the kebab case error prefix of a type can always be translated. JSON
+/// translations are flat objects from the key to a Fluent pattern, which may be empty or start a
+/// line with any character. The parameters of the ErrorDetail are the variables of the pattern
+///
+/// ```
+/// use kern::building_blocks::error::error_detail::ErrorDetail;
+/// use kern::infrastructure::localization::message_catalog::{MessageCatalog, accepted_locales};
+///
+/// let mut catalog = MessageCatalog::new("en").unwrap();
+/// catalog
+///     .add_fluent("de", "error_tenant_invalid-name = Der Name ist länger als { $max } Zeichen")
+///     .unwrap()
+///     .add_json("en", r#"{ "error.tenant.invalid-name": "The name exceeds { $max } characters" }"#)
+///     .unwrap();
+///
+/// let detail = ErrorDetail::new("error.tenant.invalid-name", "Invalid name").with_param("max", 20);
+/// let localized = catalog.localize(&detail, &accepted_locales("de-CH, en;q=0.5"));
+///
+/// assert_eq!(localized.message(), "Der Name ist länger als 20 Zeichen");
+/// ```
+pub struct MessageCatalog {
+    /// The locale that is used if none of the requested locales has a translation
+    fallback: LanguageIdentifier,
+    /// The translations per locale
+    bundles: HashMap<LanguageIdentifier, FluentBundle<FluentResource>>,
+}
+
+impl MessageCatalog {
+    /// Creates an empty MessageCatalog
+    /// # Arguments
+    /// * `fallback_locale` - The locale that is used if none of the requested locales has a
+    ///   translation
+    pub fn new(fallback_locale: &str) -> Result<Self, CatalogError> {
+        Ok(Self {
+            fallback: parse_locale(fallback_locale)?,
+            bundles: HashMap::new(),
+        })
+    }
+
+    /// Creates a MessageCatalog from the `<locale>.ftl` and `<locale>.json` files of a directory.
+    /// Other files are ignored
+    /// # Arguments
+    /// * `fallback_locale` - The locale that is used if none of the requested locales has a
+    ///   translation
+    /// * `dir` - The directory of the translation files
+    pub fn from_dir<P: AsRef<Path>>(fallback_locale: &str, dir: P) -> Result<Self, CatalogError> {
+        let mut catalog = Self::new(fallback_locale)?;
+        let mut paths = std::fs::read_dir(dir)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(CatalogError::Read)?;
+        paths.sort();
+
+        for path in paths {
+            let (Some(locale), Some(extension)) = (
+                path.file_stem().and_then(|stem| stem.to_str()),
+                path.extension().and_then(|extension| extension.to_str()),
+            ) else {
+                continue;
+            };
+            match extension {
+                "ftl" => {
+                    let source = std::fs::read_to_string(&path).map_err(CatalogError::Read)?;
+                    catalog.add_fluent(locale, &source)?;
+                }
+                "json" => {
+                    let source = std::fs::read_to_string(&path).map_err(CatalogError::Read)?;
+                    catalog.add_json(locale, &source)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(catalog)
+    }
+
+    /// Adds Fluent translations for a locale
+    /// # Arguments
+    /// * `locale` - The locale of the translations, e.g. `de-CH`
+    /// * `source` - The Fluent resource
+    pub fn add_fluent(&mut self, locale: &str, source: &str) -> Result<&mut Self, CatalogError> {
+        let invalid = |reason: String| CatalogError::Invalid {
+            locale: locale.to_string(),
+            reason,
+        };
+        let resource = FluentResource::try_new(source.to_string()).map_err(|(_, errors)| {
+            invalid(
+                errors
+                    .iter()
+                    .map(|error| error.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        })?;
+
+        let locale_id = parse_locale(locale)?;
+        self.bundles
+            .entry(locale_id.clone())
+            .or_insert_with(|| {
+                let mut bundle = FluentBundle::new_concurrent(vec![locale_id]);
+                bundle.set_use_isolating(false);
+                bundle
+            })
+            .add_resource(resource)
+            .map_err(|errors| {
+                invalid(
+                    errors
+                        .iter()
+                        .map(|error| error.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )
+            })?;
+        Ok(self)
+    }
+
+    /// Adds JSON translations for a locale
+    /// # Arguments
+    /// * `locale` - The locale of the translations, e.g. `de-CH`
+    /// * `source` - A JSON object from the error key to a Fluent pattern
+    pub fn add_json(&mut self, locale: &str, source: &str) -> Result<&mut Self, CatalogError> {
+        let invalid = |reason: String| CatalogError::Invalid {
+            locale: locale.to_string(),
+            reason,
+        };
+        let messages: BTreeMap<String, String> =
+            serde_json::from_str(source).map_err(|error| invalid(error.to_string()))?;
+
+        let mut fluent = String::new();
+        for (key, pattern) in messages {
+            let id = fluent_id(&key)
+                .ok_or_else(|| invalid(format!("'{key}' is not a translatable error key")))?;
+            fluent.push_str(&id);
+            fluent.push_str(" =");
+            // Every line starts with an empty placeable, so an empty pattern is a valid message and
+            // the first character of a line is never read as Fluent syntax, e.g. `[`, `*` or `.`
+            for (index, line) in pattern.split('\n').enumerate() {
+                fluent.push_str(if index == 0 { " " } else { "\n    " });
+                fluent.push_str("{\"\"}");
+                fluent.push_str(line.trim_end_matches('\r'));
+            }
+            fluent.push('\n');
+        }
+        self.add_fluent(locale, &fluent)
+    }
+
+    /// Resolves a key and its parameters into the message of the first requested locale that
+    /// translates the key. A requested locale with a region falls back to its language, and the
+    /// requested locales fall back to the fallback locale
+    /// # Arguments
+    /// * `key` - The error key
+    /// * `params` - The named parameters of the error
+    /// * `locales` - The requested locales, most preferred first
+    pub fn message(
+        &self,
+        key: &str,
+        params: &BTreeMap<Cow<'static, str>, serde_json::Value>,
+        locales: &[LanguageIdentifier],
+    ) -> Option<String> {
+        let id = fluent_id(key)?;
+        let mut args = FluentArgs::new();
+        for (name, value) in params {
+            args.set(name.as_ref(), fluent_value(value));
+        }
+
+        locales
+            .iter()
+            .flat_map(|locale| {
+                [
+                    locale.clone(),
+                    LanguageIdentifier::from_parts(locale.language, None, None, &[]),
+                ]
+            })
+            .chain(std::iter::once(self.fallback.clone()))
+            .filter_map(|locale| self.bundles.get(&locale))
+            .find_map(|bundle| {
+                let pattern = bundle.get_message(&id)?.value()?;
+                let mut errors = Vec::new();
+                Some(
+                    bundle
+                        .format_pattern(pattern, Some(&args), &mut errors)
+                        .into_owned(),
+                )
+            })
+    }
+
+    /// Returns the ErrorDetail with its message localized, or unchanged if no locale translates
+    /// its key
+    /// # Arguments
+    /// * `detail` - The ErrorDetail
+    /// * `locales` - The requested locales, most preferred first
+    pub fn localize(&self, detail: &ErrorDetail, locales: &[LanguageIdentifier]) -> ErrorDetail {
+        match self.message(detail.key(), detail.params(), locales) {
+            Some(message) => detail.clone().with_message(message),
+            None => detail.clone(),
+        }
+    }
+}
+
+/// Parses the locales of an `Accept-Language` header, most preferred first. Invalid tags, the
+/// wildcard and locales with a quality of 0 are skipped
+/// # Arguments
+/// * `accept_language` - The value of the header, e.g. `de-CH, de;q=0.9, en;q=0.5`
+pub fn accepted_locales(accept_language: &str) -> Vec<LanguageIdentifier> {
+    let mut locales: Vec<(LanguageIdentifier, f32)> = accept_language
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let locale = parts.next()?.trim();
+            let quality = parts
+                .filter_map(|parameter| parameter.trim().strip_prefix("q="))
+                .find_map(|quality| quality.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if locale == "*" || quality <= 0.0 {
+                return None;
+            }
+            Some((locale.parse().ok()?, quality))
+        })
+        .collect();
+    locales.sort_by(|(_, left), (_, right)| right.total_cmp(left));
+    locales.into_iter().map(|(locale, _)| locale).collect()
+}
+
+/// Parses a locale
+/// # Arguments
+/// * `locale` - The locale, e.g. `de-CH`
+fn parse_locale(locale: &str) -> Result<LanguageIdentifier, CatalogError> {
+    locale
+        .parse()
+        .map_err(|_| CatalogError::InvalidLocale(locale.to_string()))
+}
+
+/// The Fluent identifier of an error key, or None if the key cannot be translated. Only keys that
+/// start with a letter and consist of letters, digits, dots and hyphens have an identifier, which
+/// makes writing the dots as underscores injective
+/// # Arguments
+/// * `key` - The error key
+fn fluent_id(key: &str) -> Option<String> {
+    let translatable = key.starts_with(|char: char| char.is_ascii_alphabetic())
+        && key
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '.' || char == '-');
+    translatable.then(|| key.replace('.', "_"))
+}
+
+/// The Fluent variable of a parameter
+/// # Arguments
+/// * `value` - The value of the parameter
+fn fluent_value(value: &serde_json::Value) -> FluentValue<'_> {
+    match value {
+        serde_json::Value::Null => FluentValue::None,
+        serde_json::Value::Number(number) => match number.as_i64() {
+            Some(number) => number.into(),
+            None => number.as_f64().unwrap_or_default().into(),
+        },
+        serde_json::Value::String(string) => string.as_str().into(),
+        value => value.to_string().into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn catalog() -> MessageCatalog {
+        let mut catalog = MessageCatalog::new("en").unwrap();
+        catalog
+            .add_fluent(
+                "en",
+                "error_tenant_invalid-name = The name must have between { $min } and { $max } characters\n\
+                 error_tenant_not-found = The tenant does not exist",
+            )
+            .unwrap()
+            .add_json(
+                "de",
+                r#"{ "error.tenant.invalid-name": "Der Name muss zwischen { $min } und { $max } Zeichen haben" }"#,
+            )
+            .unwrap();
+        catalog
+    }
+
+    #[test]
+    fn given_an_accept_language_header_when_parsing_then_locales_are_ordered_by_quality() {
+        let locales = accepted_locales("en;q=0.5, de-CH, fr;q=0, *;q=0.1, de;q=0.9");
+
+        assert_eq!(
+            locales
+                .iter()
+                .map(|locale| locale.to_string())
+                .collect::<Vec<_>>(),
+            vec!["de-CH", "de", "en"]
+        );
+    }
+
+    #[test]
+    fn given_a_regional_locale_when_localizing_then_its_language_and_the_fallback_are_used() {
+        let catalog = catalog();
+        let detail = ErrorDetail::new("error.tenant.invalid-name", "Invalid name")
+            .with_param("min", 3)
+            .with_param("max", 20)
+            .with_field("/name");
+
+        let localized = catalog.localize(&detail, &accepted_locales("de-CH"));
+        assert_eq!(
+            localized.message(),
+            "Der Name muss zwischen 3 und 20 Zeichen haben"
+        );
+        assert_eq!(localized.field(), Some("/name"));
+
+        let not_found = ErrorDetail::new("error.tenant.not-found", "Not found");
+        assert_eq!(
+            catalog
+                .localize(&not_found, &accepted_locales("de-CH"))
+                .message(),
+            "The tenant does not exist"
+        );
+
+        let unknown = ErrorDetail::new("error.tenant.unknown", "Unknown");
+        assert_eq!(catalog.localize(&unknown, &[]).message(), "Unknown");
+    }
+
+    #[test]
+    fn given_json_patterns_with_fluent_syntax_when_localizing_then_they_are_kept_verbatim() {
+        let mut catalog = MessageCatalog::new("en").unwrap();
+        catalog
+            .add_json(
+                "en",
+                r#"{
+                    "error.tenant.empty": "",
+                    "error.tenant.list": "[x] first\n* second\n.third\n  { $name }",
+                    "error.tenant.option": "[default]"
+                }"#,
+            )
+            .unwrap();
+        let message = |key: &str| {
+            catalog
+                .localize(
+                    &ErrorDetail::new(key.to_string(), "Untranslated").with_param("name", "a"),
+                    &[],
+                )
+                .message()
+                .to_string()
+        };
+
+        assert_eq!(message("error.tenant.empty"), "");
+        assert_eq!(
+            message("error.tenant.list"),
+            "[x] first\n* second\n.third\n  a"
+        );
+        assert_eq!(message("error.tenant.option"), "[default]");
+    }
+
+    #[test]
+    fn given_keys_with_other_characters_when_translating_then_they_do_not_share_an_identifier() {
+        let mut catalog = MessageCatalog::new("en").unwrap();
+        catalog
+            .add_fluent("en", "error_tenant_name = Translated")
+            .unwrap();
+
+        assert_eq!(
+            fluent_id("error.tenant.name").as_deref(),
+            Some("error_tenant_name")
+        );
+        assert!(fluent_id("error.tenant_name").is_none());
+        assert_eq!(
+            catalog
+                .localize(&ErrorDetail::new("error.tenant_name", "Untranslated"), &[])
+                .message(),
+            "Untranslated"
+        );
+        assert!(matches!(
+            catalog.add_json("en", r#"{ "error.tenant_name": "Translated" }"#),
+            Err(CatalogError::Invalid { .. })
+        ));
+    }
+
+    #[derive(crate::ValueObject, Debug)]
+    struct PurchaseOrder {
+        number: u32,
+    }
+
+    #[test]
+    fn given_a_key_of_a_multi_word_type_when_localizing_then_the_translation_is_used() {
+        use crate::{
+            application::error::concurrency_conflict::ConcurrencyConflict,
+            building_blocks::type_name::TypeName,
+        };
+
+        let mut catalog = MessageCatalog::new("en").unwrap();
+        catalog
+            .add_json(
+                "de",
+                r#"{ "error.purchase-order.concurrency-conflict": "Die Bestellung wurde zwischenzeitlich geändert" }"#,
+            )
+            .unwrap();
+        let detail = ConcurrencyConflict::new(PurchaseOrder::error_prefix(), 1, 2).error_detail();
+
+        assert_eq!(detail.key(), "error.purchase-order.concurrency-conflict");
+        assert_eq!(
+            catalog.localize(&detail, &accepted_locales("de")).message(),
+            "Die Bestellung wurde zwischenzeitlich geändert"
+        );
+    }
+
+    #[test]
+    fn given_invalid_translations_when_adding_them_then_an_error_is_returned() {
+        let mut catalog = MessageCatalog::new("en").unwrap();
+
+        assert!(matches!(
+            catalog.add_json("de", "[]"),
+            Err(CatalogError::Invalid { .. })
+        ));
+        assert!(matches!(
+            catalog.add_fluent("not a locale!", "key = value"),
+            Err(CatalogError::InvalidLocale(_))
+        ));
+        assert!(MessageCatalog::new("").is_err());
+    }
+}
@@ -0,0 +1,6 @@
+#[cfg(feature = "in_memory")]
+pub mod in_memory_repository;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_repository;
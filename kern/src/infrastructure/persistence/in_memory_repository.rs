@@ -0,0 +1,211 @@
+use std::{collections::HashMap, sync::RwLock};
+
+use crate::{
+    application::{
+        error::{concurrency_conflict::ConcurrencyConflict, repository_error::RepositoryError},
+        repository::Repository,
+    },
+    building_blocks::{aggregate::Aggregate, entity::Entity},
+};
+
+/// The InMemoryRepository is a Repository that keeps the Aggregates in a HashMap. Useful for tests
+/// and prototypes that do not need the Aggregates to outlive the process
+pub struct InMemoryRepository<A>
+where
+    A: Aggregate + Entity,
+{
+    aggregates: RwLock<HashMap<A::Id, A>>,
+}
+
+impl<A> InMemoryRepository<A>
+where
+    A: Aggregate + Entity,
+    A::Id: Eq,
+{
+    /// Creates a new, empty InMemoryRepository
+    pub fn new() -> Self {
+        Self {
+            aggregates: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// The number of Aggregates that are stored
+    pub fn len(&self) -> usize {
+        self.aggregates.read().map(|map| map.len()).unwrap_or(0)
+    }
+
+    /// Returns true if no Aggregates are stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<A> Default for InMemoryRepository<A>
+where
+    A: Aggregate + Entity,
+    A::Id: Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl<A> Repository<A> for InMemoryRepository<A>
+where
+    A: Aggregate + Entity + Clone + Send + Sync,
+    A::Id: Eq,
+{
+    async fn find_by_id(&self, id: &A::Id) -> Result<Option<A>, RepositoryError> {
+        let aggregates = self
+            .aggregates
+            .read()
+            .map_err(|err| RepositoryError::storage(err.to_string()))?;
+        Ok(aggregates.get(id).cloned())
+    }
+
+    async fn save(&self, aggregate: &mut A) -> Result<(), RepositoryError> {
+        let mut aggregates = self
+            .aggregates
+            .write()
+            .map_err(|err| RepositoryError::storage(err.to_string()))?;
+        let actual_version = aggregates.get(aggregate.id()).map_or(0, |a| a.version());
+        if actual_version != aggregate.version() {
+            return Err(ConcurrencyConflict::new(
+                A::error_prefix(),
+                aggregate.version(),
+                actual_version,
+            )
+            .into());
+        }
+        aggregate.increment_version()?;
+        aggregates.insert(aggregate.id().clone(), aggregate.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, id: &A::Id, expected_version: u32) -> Result<(), RepositoryError> {
+        let mut aggregates = self
+            .aggregates
+            .write()
+            .map_err(|err| RepositoryError::storage(err.to_string()))?;
+        let actual_version = aggregates.get(id).map_or(0, |a| a.version());
+        if actual_version == 0 || actual_version != expected_version {
+            return Err(ConcurrencyConflict::new(
+                A::error_prefix(),
+                expected_version,
+                actual_version,
+            )
+            .into());
+        }
+        aggregates.remove(id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uuid::Uuid;
+
+    // The derive macros refer to the crate by its name
+    use crate as kern;
+
+    #[derive(crate::Aggregate, Clone, Debug)]
+    struct Tenant {
+        #[generate_id(Uuid)]
+        #[entity_id]
+        id: TenantId,
+        #[field]
+        name: String,
+        version: u32,
+    }
+
+    impl Tenant {
+        fn new(name: &str) -> Self {
+            Self {
+                id: TenantId::new(Uuid::new_v4()),
+                name: name.to_string(),
+                version: 0,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn given_a_new_aggregate_when_saving_then_it_can_be_loaded() {
+        let repository = InMemoryRepository::<Tenant>::new();
+        let mut tenant = Tenant::new("tenant.a");
+
+        repository.save(&mut tenant).await.unwrap();
+        let loaded = repository.find_by_id(tenant.id()).await.unwrap().unwrap();
+
+        assert_eq!(tenant.version(), 1);
+        assert_eq!(loaded.version(), 1);
+        assert_eq!(loaded.name(), "tenant.a");
+    }
+
+    #[tokio::test]
+    async fn given_a_stale_aggregate_when_saving_then_a_conflict_is_returned() {
+        let repository = InMemoryRepository::<Tenant>::new();
+        let mut tenant = Tenant::new("tenant.a");
+        repository.save(&mut tenant).await.unwrap();
+
+        let mut first = repository.find_by_id(tenant.id()).await.unwrap().unwrap();
+        let mut second = first.clone();
+        repository.save(&mut first).await.unwrap();
+
+        match repository.save(&mut second).await {
+            Err(RepositoryError::ConcurrencyConflict(conflict)) => {
+                assert_eq!(conflict.expected_version(), 1);
+                assert_eq!(conflict.actual_version(), 2);
+                assert_eq!(
+                    conflict.error_detail().key(),
+                    "error.tenant.concurrency-conflict"
+                );
+            }
+            other => panic!("Expected a ConcurrencyConflict, got {other:?}"),
+        }
+        assert_eq!(second.version(), 1);
+    }
+
+    #[tokio::test]
+    async fn given_an_aggregate_at_the_highest_version_when_saving_then_a_domain_error_is_returned()
+    {
+        use crate::{
+            application::error::application_error::ApplicationError,
+            building_blocks::aggregate::VERSION_OVERFLOW,
+        };
+
+        let repository = InMemoryRepository::<Tenant>::new();
+        let mut tenant = Tenant::new("tenant.a");
+        tenant.version = u32::MAX;
+        repository
+            .aggregates
+            .write()
+            .unwrap()
+            .insert(*tenant.id(), tenant.clone());
+
+        let err = repository.save(&mut tenant).await.unwrap_err();
+
+        assert!(
+            matches!(&err, RepositoryError::Domain(error) if *error == VERSION_OVERFLOW.into())
+        );
+        assert!(matches!(
+            ApplicationError::from(err),
+            ApplicationError::Domain(_)
+        ));
+        assert_eq!(tenant.version(), u32::MAX);
+    }
+
+    #[tokio::test]
+    async fn given_a_stored_aggregate_when_deleting_then_only_the_expected_version_is_removed() {
+        let repository = InMemoryRepository::<Tenant>::new();
+        let mut tenant = Tenant::new("tenant.a");
+        repository.save(&mut tenant).await.unwrap();
+
+        assert!(repository.delete(tenant.id(), 0).await.is_err());
+        repository.delete(tenant.id(), 1).await.unwrap();
+
+        assert!(repository.find_by_id(tenant.id()).await.unwrap().is_none());
+        assert!(repository.is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use rusqlite::Connection;
+
+/// The schema shared by the SQLite backed Repository and EventStore
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS aggregates (
+    aggregate_type TEXT NOT NULL,
+    aggregate_id TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    data TEXT NOT NULL,
+    PRIMARY KEY (aggregate_type, aggregate_id)
+);
+
+CREATE TABLE IF NOT EXISTS events (
+    position INTEGER PRIMARY KEY AUTOINCREMENT,
+    aggregate_type TEXT NOT NULL,
+    aggregate_id TEXT NOT NULL,
+    aggregate_version INTEGER NOT NULL,
+    event_id TEXT NOT NULL UNIQUE,
+    occurred_at TEXT NOT NULL,
+    data TEXT NOT NULL,
+    UNIQUE (aggregate_type, aggregate_id, aggregate_version)
+);
+
+CREATE TABLE IF NOT EXISTS outbox (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    topic TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    retry_at INTEGER,
+    dispatched_at TEXT,
+    parked_at TEXT,
+    data TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS outbox_pending ON outbox (topic, id)
+    WHERE dispatched_at IS NULL AND parked_at IS NULL;
+";
+
+/// The SqliteDatabase is a handle to an embedded SQLite database. It is cheap to clone, so the
+/// SQLite backed Repositories and EventStores of one service can share the same database.
+///
+/// The SQLite calls run on the blocking thread pool of tokio, so they do not stall the async
+/// executor. A SqliteDatabase has a single connection, which serializes every access to it. Open
+/// the database file once per component that needs to access it in parallel
+#[derive(Clone)]
+pub struct SqliteDatabase {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteDatabase {
+    /// Opens the SQLite database file, creating it and its tables if they do not exist yet
+    /// # Arguments
+    /// * `path` - The path of the SQLite database file
+    pub fn open<P>(path: P) -> Result<Self, rusqlite::Error>
+    where
+        P: AsRef<Path>,
+    {
+        let connection = Connection::open(path)?;
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+        connection.busy_timeout(std::time::Duration::from_secs(5))?;
+        Self::new(connection)
+    }
+
+    /// Opens a SQLite database that only lives in memory. Useful for tests
+    pub fn open_in_memory() -> Result<Self, rusqlite::Error> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    fn new(connection: Connection) -> Result<Self, rusqlite::Error> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// Runs the SQLite calls with the locked connection on the blocking thread pool of tokio.
+    /// Returns an error if the lock is poisoned or the calls panicked
+    /// # Arguments
+    /// * `calls` - The SQLite calls
+    pub(crate) async fn run_blocking<T, F>(&self, calls: F) -> Result<T, String>
+    where
+        F: FnOnce(&mut Connection) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let connection = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = connection.lock().map_err(|err| err.to_string())?;
+            Ok(calls(&mut connection))
+        })
+        .await
+        .map_err(|err| err.to_string())?
+    }
+
+    /// Returns true if both handles refer to the same database connection
+    pub(crate) fn is_same(&self, other: &SqliteDatabase) -> bool {
+        Arc::ptr_eq(&self.connection, &other.connection)
+    }
+}
+
+/// Returns true if the error is a violation of a UNIQUE or PRIMARY KEY constraint whose message
+/// mentions the column
+/// # Arguments
+/// * `error` - The error returned by SQLite
+/// * `column` - The column of the constraint
+pub(crate) fn is_unique_violation(error: &rusqlite::Error, column: &str) -> bool {
+    matches!(
+        error,
+        rusqlite::Error::SqliteFailure(failure, Some(message))
+            if failure.code == rusqlite::ErrorCode::ConstraintViolation
+                && message.contains(column)
+    )
+}
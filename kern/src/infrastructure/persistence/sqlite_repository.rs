@@ -0,0 +1,323 @@
+use std::marker::PhantomData;
+
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    application::{
+        error::{concurrency_conflict::ConcurrencyConflict, repository_error::RepositoryError},
+        repository::Repository,
+    },
+    building_blocks::{aggregate::Aggregate, entity::Entity},
+    infrastructure::{
+        event::sqlite_outbox::{SqliteOutbox, enqueue_with},
+        persistence::sqlite::SqliteDatabase,
+    },
+};
+
+/// The SqliteRepository is a Repository that stores the Aggregates as JSON in the `aggregates`
+/// table of a SqliteDatabase. The `version` column is used for compare-and-swap updates, so a
+/// stale write fails with a `ConcurrencyConflict` instead of overwriting a newer Aggregate
+pub struct SqliteRepository<A> {
+    database: SqliteDatabase,
+    _aggregate: PhantomData<fn() -> A>,
+}
+
+impl<A> SqliteRepository<A> {
+    /// Creates a new SqliteRepository
+    /// # Arguments
+    /// * `database` - The database the Aggregates are stored in
+    pub fn new(database: SqliteDatabase) -> Self {
+        Self {
+            database,
+            _aggregate: PhantomData,
+        }
+    }
+}
+
+/// The version of the Aggregate that is currently stored, or 0 if it is not stored
+/// # Arguments
+/// * `connection` - The connection to the SQLite database
+/// * `aggregate_type` - The type name of the Aggregate
+/// * `aggregate_id` - The JSON encoded identifier of the Aggregate
+fn stored_version(
+    connection: &Connection,
+    aggregate_type: &str,
+    aggregate_id: &str,
+) -> Result<u32, RepositoryError> {
+    connection
+        .query_row(
+            "SELECT version FROM aggregates WHERE aggregate_type = ?1 AND aggregate_id = ?2",
+            params![aggregate_type, aggregate_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map(|version| version.unwrap_or(0))
+        .map_err(RepositoryError::storage)
+}
+
+/// Saves the Aggregate using the connection, so the caller controls the transaction. Returns the
+/// Aggregate with its incremented version
+/// # Arguments
+/// * `connection` - The connection or transaction to the SQLite database
+/// * `aggregate` - The Aggregate to save
+fn save_with<A>(connection: &Connection, aggregate: &A) -> Result<A, RepositoryError>
+where
+    A: Aggregate + Entity + Clone + Serialize,
+    A::Id: Serialize,
+{
+    let expected_version = aggregate.version();
+    let mut next = aggregate.clone();
+    next.increment_version()?;
+
+    let aggregate_id = serde_json::to_string(aggregate.id()).map_err(RepositoryError::storage)?;
+    let data = serde_json::to_string(&next).map_err(RepositoryError::storage)?;
+
+    let rows = if expected_version == 0 {
+        connection.execute(
+            "INSERT INTO aggregates (aggregate_type, aggregate_id, version, data)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT DO NOTHING",
+            params![A::type_name(), aggregate_id, next.version(), data],
+        )
+    } else {
+        connection.execute(
+            "UPDATE aggregates SET version = ?3, data = ?4
+             WHERE aggregate_type = ?1 AND aggregate_id = ?2 AND version = ?5",
+            params![
+                A::type_name(),
+                aggregate_id,
+                next.version(),
+                data,
+                expected_version
+            ],
+        )
+    }
+    .map_err(RepositoryError::storage)?;
+
+    if rows == 0 {
+        let actual_version = stored_version(connection, A::type_name(), &aggregate_id)?;
+        return Err(
+            ConcurrencyConflict::new(A::error_prefix(), expected_version, actual_version).into(),
+        );
+    }
+    Ok(next)
+}
+
+impl<A> SqliteRepository<A>
+where
+    A: Aggregate + Entity + Clone + Serialize + Send + Sync + 'static,
+    A::Id: Serialize,
+{
+    /// Saves the Aggregate and enqueues its Domain Events in the Outbox in a single transaction,
+    /// so either both are stored or neither is. The Outbox must use the same SqliteDatabase
+    /// # Arguments
+    /// * `aggregate` - The Aggregate to store
+    /// * `outbox` - The Outbox the Domain Events are enqueued in
+    /// * `events` - The Domain Events the Aggregate raised
+    pub async fn save_with_outbox<E>(
+        &self,
+        aggregate: &mut A,
+        outbox: &SqliteOutbox<E>,
+        events: Vec<E>,
+    ) -> Result<(), RepositoryError>
+    where
+        E: Serialize + Send + Sync + 'static,
+    {
+        if !self.database.is_same(outbox.database()) {
+            return Err(RepositoryError::storage(
+                "The outbox must use the same database as the repository",
+            ));
+        }
+        let current = aggregate.clone();
+        let topic = outbox.topic();
+        *aggregate = self
+            .database
+            .run_blocking(move |connection| {
+                let transaction = connection.transaction().map_err(RepositoryError::storage)?;
+                let next = save_with(&transaction, &current)?;
+                enqueue_with(&transaction, topic, &events).map_err(RepositoryError::storage)?;
+                transaction.commit().map_err(RepositoryError::storage)?;
+                Ok::<_, RepositoryError>(next)
+            })
+            .await
+            .map_err(RepositoryError::storage)??;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<A> Repository<A> for SqliteRepository<A>
+where
+    A: Aggregate + Entity + Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+    A::Id: Serialize,
+{
+    async fn find_by_id(&self, id: &A::Id) -> Result<Option<A>, RepositoryError> {
+        let aggregate_id = serde_json::to_string(id).map_err(RepositoryError::storage)?;
+        self.database
+            .run_blocking(move |connection| {
+                let data: Option<String> = connection
+                    .query_row(
+                        "SELECT data FROM aggregates
+                         WHERE aggregate_type = ?1 AND aggregate_id = ?2",
+                        params![A::type_name(), aggregate_id],
+                        |row| row.get(0),
+                    )
+                    .optional()
+                    .map_err(RepositoryError::storage)?;
+                data.map(|data| serde_json::from_str(&data).map_err(RepositoryError::storage))
+                    .transpose()
+            })
+            .await
+            .map_err(RepositoryError::storage)?
+    }
+
+    async fn save(&self, aggregate: &mut A) -> Result<(), RepositoryError> {
+        let current = aggregate.clone();
+        *aggregate = self
+            .database
+            .run_blocking(move |connection| save_with(connection, &current))
+            .await
+            .map_err(RepositoryError::storage)??;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &A::Id, expected_version: u32) -> Result<(), RepositoryError> {
+        let aggregate_id = serde_json::to_string(id).map_err(RepositoryError::storage)?;
+        self.database
+            .run_blocking(move |connection| {
+                let rows = connection
+                    .execute(
+                        "DELETE FROM aggregates
+                         WHERE aggregate_type = ?1 AND aggregate_id = ?2 AND version = ?3",
+                        params![A::type_name(), aggregate_id, expected_version],
+                    )
+                    .map_err(RepositoryError::storage)?;
+
+                if rows == 0 {
+                    let actual_version = stored_version(connection, A::type_name(), &aggregate_id)?;
+                    return Err(ConcurrencyConflict::new(
+                        A::error_prefix(),
+                        expected_version,
+                        actual_version,
+                    )
+                    .into());
+                }
+                Ok(())
+            })
+            .await
+            .map_err(RepositoryError::storage)?
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::Deserialize;
+    use uuid::Uuid;
+
+    // The derive macros refer to the crate by its name
+    use crate as kern;
+
+    #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Serialize, Deserialize)]
+    struct TenantId(Uuid);
+
+    #[derive(crate::Aggregate, Clone, Debug, Serialize, Deserialize)]
+    struct Tenant {
+        #[entity_id]
+        id: TenantId,
+        #[field]
+        name: String,
+        version: u32,
+    }
+
+    impl Tenant {
+        fn new(name: &str) -> Self {
+            Self {
+                id: TenantId(Uuid::new_v4()),
+                name: name.to_string(),
+                version: 0,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn given_a_new_aggregate_when_saving_then_it_can_be_loaded() {
+        let repository = SqliteRepository::<Tenant>::new(SqliteDatabase::open_in_memory().unwrap());
+        let mut tenant = Tenant::new("tenant.a");
+
+        repository.save(&mut tenant).await.unwrap();
+        tenant.name = "tenant.b".to_string();
+        repository.save(&mut tenant).await.unwrap();
+        let loaded = repository.find_by_id(tenant.id()).await.unwrap().unwrap();
+
+        assert_eq!(tenant.version(), 2);
+        assert_eq!(loaded.version(), 2);
+        assert_eq!(loaded.name(), "tenant.b");
+    }
+
+    #[tokio::test]
+    async fn given_a_stale_aggregate_when_saving_then_a_conflict_is_returned() {
+        let repository = SqliteRepository::<Tenant>::new(SqliteDatabase::open_in_memory().unwrap());
+        let mut tenant = Tenant::new("tenant.a");
+        let mut duplicate = tenant.clone();
+        repository.save(&mut tenant).await.unwrap();
+
+        match repository.save(&mut duplicate).await {
+            Err(RepositoryError::ConcurrencyConflict(conflict)) => {
+                assert_eq!(conflict.expected_version(), 0);
+                assert_eq!(conflict.actual_version(), 1);
+            }
+            other => panic!("Expected a ConcurrencyConflict, got {other:?}"),
+        }
+
+        let mut first = repository.find_by_id(tenant.id()).await.unwrap().unwrap();
+        let mut second = first.clone();
+        repository.save(&mut first).await.unwrap();
+
+        assert!(matches!(
+            repository.save(&mut second).await,
+            Err(RepositoryError::ConcurrencyConflict(_))
+        ));
+        assert_eq!(second.version(), 1);
+    }
+
+    #[tokio::test]
+    async fn given_a_stale_aggregate_when_saving_with_outbox_then_no_message_is_enqueued() {
+        use crate::application::outbox::Outbox;
+
+        let database = SqliteDatabase::open_in_memory().unwrap();
+        let repository = SqliteRepository::<Tenant>::new(database.clone());
+        let outbox = SqliteOutbox::<String>::new(database, "tenant");
+        let mut tenant = Tenant::new("tenant.a");
+        let mut stale = tenant.clone();
+
+        repository
+            .save_with_outbox(&mut tenant, &outbox, vec!["created".to_string()])
+            .await
+            .unwrap();
+        let result = repository
+            .save_with_outbox(&mut stale, &outbox, vec!["duplicate".to_string()])
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(RepositoryError::ConcurrencyConflict(_))
+        ));
+        let pending = outbox.pending(10).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].event().as_str(), "created");
+    }
+
+    #[tokio::test]
+    async fn given_a_stored_aggregate_when_deleting_then_only_the_expected_version_is_removed() {
+        let repository = SqliteRepository::<Tenant>::new(SqliteDatabase::open_in_memory().unwrap());
+        let mut tenant = Tenant::new("tenant.a");
+        repository.save(&mut tenant).await.unwrap();
+
+        assert!(repository.delete(tenant.id(), 2).await.is_err());
+        repository.delete(tenant.id(), 1).await.unwrap();
+
+        assert!(repository.find_by_id(tenant.id()).await.unwrap().is_none());
+    }
+}
@@ -2,63 +2,212 @@ pub mod application;
 pub mod building_blocks;
 pub mod infrastructure;
 
+// The macros of ddd_macros refer to `kern::`, which resolves inside this crate as well
+extern crate self as kern;
+
 pub use ddd_macros::*;
+#[cfg(feature = "error_catalog")]
+#[doc(hidden)]
+pub use linkme;
+#[doc(hidden)]
+pub use serde;
+#[doc(hidden)]
+pub use utoipa;
+#[doc(hidden)]
+pub use uuid;
 
 #[cfg(feature = "validator")]
 pub mod validator_extensions {
-    use std::collections::HashSet;
-
     use crate::building_blocks::{
-        aggregate::Aggregate,
-        error::{domain_error::DomainError, error_detail::ErrorDetail},
+        error::{
+            domain_error::DomainError, error_detail::ErrorDetail, error_details::ErrorDetails,
+        },
+        type_name::TypeName,
     };
-    use validator::ValidationErrors;
+    use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
 
-    pub trait ResultValidation<T: Aggregate> {
+    pub trait ResultValidation<T: TypeName> {
         fn to_domain_error(self) -> Result<T, DomainError>;
     }
 
     impl<T> ResultValidation<T> for Result<T, ValidationErrors>
     where
-        T: Aggregate,
+        T: TypeName,
     {
         fn to_domain_error(self) -> Result<T, DomainError> {
             self.map_err(|err| {
-                let errors = err
-                    .0
-                    .into_iter()
-                    .filter_map(|(key, value)| {
-                        if let validator::ValidationErrorsKind::Field(errors) = value {
-                            Some((key, errors))
-                        } else {
-                            None
-                        }
-                    })
-                    .flat_map(|(key, errors)| {
-                        let type_name = T::type_name();
-                        let error_key = {
-                            let mut error_key =
-                                String::with_capacity(16 + type_name.len() + key.len());
-                            error_key.push_str("error.");
-                            error_key.push_str(type_name);
-                            error_key.push_str(".invalid-");
-                            error_key.extend(
-                                key.as_ref().chars().map(|c| if c == '_' { '-' } else { c }),
-                            );
-                            error_key
-                        };
-                        errors.into_iter().filter_map(move |error| {
-                            error.message.as_deref().map(|message| {
-                                ErrorDetail::new(
-                                    error_key.clone(),
-                                    format!("'{}' {}", key, message),
-                                )
-                            })
-                        })
-                    })
-                    .collect::<HashSet<ErrorDetail>>();
+                let mut errors = ErrorDetails::new();
+                flatten(T::error_prefix(), &mut Vec::new(), err, &mut errors);
                 DomainError::multiple(errors)
             })
         }
     }
+
+    /// Flattens the errors of a struct, its nested structs and the items of its lists into
+    /// ErrorDetails. The key of an ErrorDetail is qualified with the path of the field, e.g.
+    /// `error.order.invalid-lines-2-quantity`, and its field is the JSON pointer of the field, e.g.
+    /// `/lines/2/quantity`
+    /// # Arguments
+    /// * `error_prefix` - The error prefix of the validated type
+    /// * `path` - The path of the struct the errors belong to
+    /// * `errors` - The errors of the struct
+    /// * `details` - The ErrorDetails the errors are added to
+    fn flatten(
+        error_prefix: &str,
+        path: &mut Vec<String>,
+        errors: ValidationErrors,
+        details: &mut ErrorDetails,
+    ) {
+        for (field, kind) in errors.0 {
+            path.push(field.into_owned());
+            match kind {
+                ValidationErrorsKind::Field(errors) => {
+                    let error_key = format!(
+                        "error.{}.invalid-{}",
+                        error_prefix,
+                        path.join("-").replace('_', "-")
+                    );
+                    let pointer = format!("/{}", path.join("/"));
+                    details.extend(
+                        errors
+                            .into_iter()
+                            .map(|error| error_detail(error_key.clone(), pointer.clone(), error)),
+                    );
+                }
+                ValidationErrorsKind::Struct(errors) => {
+                    flatten(error_prefix, path, *errors, details)
+                }
+                ValidationErrorsKind::List(items) => {
+                    for (index, errors) in items {
+                        path.push(index.to_string());
+                        flatten(error_prefix, path, *errors, details);
+                        path.pop();
+                    }
+                }
+            }
+            path.pop();
+        }
+    }
+
+    /// Turns a validation error of a field into an ErrorDetail. The code of the error is used as
+    /// message if the error has none, and it is added to the params of the ErrorDetail along with
+    /// the params of the error, e.g. `min`, `max` and `value`
+    /// # Arguments
+    /// * `error_key` - The key of the ErrorDetail
+    /// * `pointer` - The JSON pointer of the field
+    /// * `error` - The validation error
+    fn error_detail(error_key: String, pointer: String, error: ValidationError) -> ErrorDetail {
+        let message = error.message.unwrap_or_else(|| error.code.clone());
+        error.params.into_iter().fold(
+            ErrorDetail::new(error_key, message)
+                .with_field(pointer)
+                .with_param("code", error.code.into_owned()),
+            |detail, (name, value)| detail.with_param(name, value),
+        )
+    }
+
+    #[cfg(test)]
+    mod test {
+        use uuid::Uuid;
+        use validator::Validate;
+
+        use super::*;
+        use crate::building_blocks::entity::Entity;
+
+        #[derive(Debug, Validate)]
+        struct Line {
+            #[validate(range(min = 1, max = 100, message = "The quantity is out of range"))]
+            quantity: u32,
+        }
+
+        #[derive(crate::ValueObject, Debug, Validate)]
+        #[error_prefix = "postal-address"]
+        struct Address {
+            #[validate(length(min = 5, max = 5))]
+            zip_code: String,
+        }
+
+        #[derive(crate::Aggregate, Debug, Validate)]
+        struct Order {
+            #[generate_id(Uuid)]
+            #[entity_id]
+            id: OrderId,
+            #[field]
+            #[validate(
+                length(min = 3, message = "The name is too short"),
+                contains(pattern = "-", message = "The name has no dash")
+            )]
+            name: String,
+            #[validate(nested)]
+            address: Address,
+            #[validate(nested)]
+            lines: Vec<Line>,
+            version: u32,
+        }
+
+        #[test]
+        fn given_nested_and_list_errors_when_mapping_then_every_error_is_flattened() {
+            let order = Order {
+                id: OrderId::new(Uuid::new_v4()),
+                name: "ab".to_string(),
+                address: Address {
+                    zip_code: "123".to_string(),
+                },
+                lines: vec![Line { quantity: 1 }, Line { quantity: 0 }],
+                version: 0,
+            };
+
+            let error = order
+                .validate()
+                .map(|_| order)
+                .to_domain_error()
+                .unwrap_err();
+            let details = error.into_error_details();
+
+            assert_eq!(
+                details
+                    .iter()
+                    .map(|detail| (detail.key(), detail.message(), detail.field().unwrap()))
+                    .collect::<Vec<_>>(),
+                vec![
+                    (
+                        "error.order.invalid-address-zip-code",
+                        "length",
+                        "/address/zip_code"
+                    ),
+                    (
+                        "error.order.invalid-lines-1-quantity",
+                        "The quantity is out of range",
+                        "/lines/1/quantity"
+                    ),
+                    ("error.order.invalid-name", "The name is too short", "/name"),
+                    ("error.order.invalid-name", "The name has no dash", "/name"),
+                ]
+            );
+            let quantity = &details.get("error.order.invalid-lines-1-quantity")[0];
+            assert_eq!(quantity.params()["min"], 1);
+            assert_eq!(quantity.params()["max"], 100);
+            assert_eq!(quantity.params()["value"], 0);
+            assert_eq!(quantity.params()["code"], "range");
+        }
+
+        #[test]
+        fn given_a_value_object_with_an_error_prefix_when_mapping_then_the_prefix_is_used() {
+            let address = Address {
+                zip_code: "123".to_string(),
+            };
+
+            let error = address
+                .validate()
+                .map(|_| address)
+                .to_domain_error()
+                .unwrap_err();
+
+            assert!(
+                error
+                    .into_error_details()
+                    .contains("error.postal-address.invalid-zip-code")
+            );
+        }
+    }
 }